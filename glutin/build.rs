@@ -15,6 +15,7 @@ fn main() {
         // Native displays.
         x11_platform: { all(feature = "x11", free_unix, not(wasm_platform)) },
         wayland_platform: { all(feature = "wayland", free_unix, not(wasm_platform)) },
+        gbm_platform: { all(feature = "gbm", free_unix, not(wasm_platform)) },
 
         // Backends.
         egl_backend: { all(feature = "egl", any(windows, unix), not(apple), not(wasm_platform)) },
@@ -272,15 +272,22 @@ impl ContextInner {
         ContextApi::OpenGl(None)
     }
 
-    pub(crate) fn set_swap_interval(&self, interval: SwapInterval) {
+    pub(crate) fn set_swap_interval(&self, interval: SwapInterval) -> Result<()> {
         let interval = match interval {
             SwapInterval::DontWait => 0,
             SwapInterval::Wait(_) => 1,
+            SwapInterval::Adaptive(_) => {
+                return Err(
+                    ErrorKind::NotSupported("adaptive swap interval is not supported").into()
+                );
+            },
         };
 
         autoreleasepool(|_| unsafe {
             self.raw.setValues_forParameter(&interval, NSOpenGLCPSwapInterval);
-        })
+        });
+
+        Ok(())
     }
 
     pub(crate) fn update(&self) {
@@ -15,7 +15,7 @@ use crate::display::GetGlDisplay;
 use crate::error::{ErrorKind, Result};
 use crate::private::Sealed;
 use crate::surface::{
-    AsRawSurface, GlSurface, PbufferSurface, PixmapSurface, RawSurface, SurfaceAttributes,
+    AsRawSurface, GlSurface, PbufferSurface, PixmapSurface, RawSurface, Rect, SurfaceAttributes,
     SurfaceTypeTrait, SwapInterval, WindowSurface,
 };
 
@@ -151,9 +151,13 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         context.inner.flush_buffer()
     }
 
+    fn swap_buffers_with_damage(&self, context: &Self::Context, _rects: &[Rect]) -> Result<()> {
+        // The damage is not supported, so swap the entire surface.
+        self.swap_buffers(context)
+    }
+
     fn set_swap_interval(&self, context: &Self::Context, interval: SwapInterval) -> Result<()> {
-        context.inner.set_swap_interval(interval);
-        Ok(())
+        context.inner.set_swap_interval(interval)
     }
 
     fn is_current(&self, context: &Self::Context) -> bool {
@@ -0,0 +1,379 @@
+//! Everything related to the application controlled cache of the compiled
+//! shader binaries.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+use std::{ffi, process};
+
+use glutin_egl_sys::egl::types::EGLsizeiANDROID;
+use once_cell::sync::OnceCell;
+
+use crate::error::{Error, ErrorKind, Result};
+
+use super::display::Display;
+
+/// The cache set for the process, since the `EGL_ANDROID_blob_cache`
+/// callbacks don't carry any user data.
+static BLOB_CACHE: OnceCell<Box<dyn BlobCache>> = OnceCell::new();
+
+/// The counter to give every temporary file of the [`FileBlobCache`] a unique
+/// name.
+static TMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// The age after which the temporary files left by the interrupted writes are
+/// removed.
+const STALE_TMP_FILE_AGE: Duration = Duration::from_secs(60);
+
+impl Display {
+    /// Set the `cache` used by the driver to store and retrieve the compiled
+    /// shader binaries.
+    ///
+    /// The `EGL_ANDROID_blob_cache` callbacks are not associated with the
+    /// display they're set for, thus the cache is global for the process and
+    /// could only be set once, for a single display. It should be set right
+    /// after creating the display, before any contexts are created.
+    ///
+    /// This function returns [`Err`] if the `EGL_ANDROID_blob_cache` extension
+    /// is not available or the cache was already set in this process.
+    pub fn set_blob_cache<C: BlobCache + 'static>(&self, cache: C) -> Result<()> {
+        if !self.inner.display_extensions.contains("EGL_ANDROID_blob_cache") {
+            return Err(ErrorKind::NotSupported("the blob cache is not supported").into());
+        }
+
+        // Register the callbacks while initializing the cache, so the cache is
+        // only stored when the registration succeeded.
+        let mut registered = false;
+        BLOB_CACHE.get_or_try_init(|| {
+            unsafe {
+                self.inner.egl.SetBlobCacheFuncsANDROID(
+                    *self.inner.raw,
+                    blob_cache_set,
+                    blob_cache_get,
+                );
+            }
+
+            super::check_error()?;
+            registered = true;
+
+            Ok::<_, Error>(Box::new(cache) as Box<dyn BlobCache>)
+        })?;
+
+        if registered {
+            Ok(())
+        } else {
+            Err(ErrorKind::BadAccess.into())
+        }
+    }
+}
+
+/// The storage of the compiled shader binaries used by the driver.
+///
+/// The cache is only a hint for the driver, thus the implementations are free
+/// to drop any values. When the implementation panics, the value being stored
+/// is dropped and the one being retrieved is treated as missing.
+pub trait BlobCache: Send + Sync {
+    /// Store the `value` for the `key`, replacing the old one.
+    fn set(&self, key: &[u8], value: &[u8]);
+
+    /// Get the value stored for the `key`.
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+}
+
+extern "system" fn blob_cache_set(
+    key: *const ffi::c_void,
+    key_size: EGLsizeiANDROID,
+    value: *const ffi::c_void,
+    value_size: EGLsizeiANDROID,
+) {
+    let cache = match BLOB_CACHE.get() {
+        Some(cache) => cache,
+        None => return,
+    };
+
+    let (key, value) = unsafe {
+        (
+            std::slice::from_raw_parts(key.cast::<u8>(), key_size as usize),
+            std::slice::from_raw_parts(value.cast::<u8>(), value_size as usize),
+        )
+    };
+
+    // Unwinding into the driver is undefined behavior.
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| cache.set(key, value)));
+}
+
+extern "system" fn blob_cache_get(
+    key: *const ffi::c_void,
+    key_size: EGLsizeiANDROID,
+    value: *mut ffi::c_void,
+    value_size: EGLsizeiANDROID,
+) -> EGLsizeiANDROID {
+    let cache = match BLOB_CACHE.get() {
+        Some(cache) => cache,
+        None => return 0,
+    };
+
+    let key = unsafe { std::slice::from_raw_parts(key.cast::<u8>(), key_size as usize) };
+
+    // Unwinding into the driver is undefined behavior.
+    let blob = match panic::catch_unwind(AssertUnwindSafe(|| cache.get(key))) {
+        Ok(Some(blob)) => blob,
+        Ok(None) | Err(_) => return 0,
+    };
+
+    // The driver will call again with the large enough buffer.
+    if blob.len() <= value_size as usize {
+        unsafe {
+            std::ptr::copy_nonoverlapping(blob.as_ptr(), value.cast::<u8>(), blob.len());
+        }
+    }
+
+    blob.len() as EGLsizeiANDROID
+}
+
+/// The [`BlobCache`] storing every value in its own file inside the
+/// directory.
+///
+/// Once the total size of the files exceeds the limit, the least recently
+/// written ones are removed.
+///
+/// ```
+/// # #[cfg(all(feature = "egl", not(target_vendor = "apple"), not(target_family = "wasm")))]
+/// # fn main() {
+/// use glutin::api::egl::blob_cache::{BlobCache, FileBlobCache};
+///
+/// let directory = std::env::temp_dir().join(format!("glutin-blob-cache-{}", std::process::id()));
+/// let cache = FileBlobCache::new(&directory, 1024);
+///
+/// cache.set(b"key", b"value");
+/// assert_eq!(cache.get(b"key"), Some(b"value".to_vec()));
+/// assert_eq!(cache.get(b"other key"), None);
+///
+/// // The values which don't fit into the cache are not stored.
+/// cache.set(b"large key", &[0; 1024]);
+/// assert_eq!(cache.get(b"large key"), None);
+/// # std::fs::remove_dir_all(&directory).unwrap();
+/// # }
+/// # #[cfg(not(all(feature = "egl", not(target_vendor = "apple"), not(target_family = "wasm"))))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileBlobCache {
+    directory: PathBuf,
+    max_size: u64,
+}
+
+impl FileBlobCache {
+    /// Create the cache inside the `directory`, which is created when the
+    /// first value is stored, taking up to `max_size` bytes.
+    pub fn new<P: Into<PathBuf>>(directory: P, max_size: u64) -> Self {
+        Self { directory: directory.into(), max_size }
+    }
+
+    /// The directory the cache is stored in.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// The path of the file storing the value for the `key`.
+    fn path(&self, key: &[u8]) -> PathBuf {
+        // The 64-bit FNV-1a hash, which is stable across the runs.
+        let hash = key.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+
+        self.directory.join(format!("{hash:016x}.blob"))
+    }
+
+    /// Remove the least recently written values until the `size` bytes fit
+    /// into the cache.
+    fn evict(&self, size: u64) {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        let files: Vec<(SystemTime, u64, PathBuf)> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let path = entry.path();
+                let metadata = entry.metadata().ok()?;
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+
+                match path.extension()? {
+                    extension if extension == "blob" && metadata.is_file() => {
+                        Some((modified, metadata.len(), path))
+                    },
+                    // Remove the files left by the interrupted writes, giving the ones
+                    // being written the time to complete.
+                    extension if extension == "tmp" => {
+                        let age = modified.elapsed().unwrap_or_default();
+                        if age > STALE_TMP_FILE_AGE {
+                            let _ = fs::remove_file(path);
+                        }
+                        None
+                    },
+                    _ => None,
+                }
+            })
+            .collect();
+
+        for path in files_to_evict(files, size, self.max_size) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Pick the least recently written `files` to remove, so the `size` bytes fit
+/// into the `max_size`.
+fn files_to_evict(
+    mut files: Vec<(SystemTime, u64, PathBuf)>,
+    size: u64,
+    max_size: u64,
+) -> Vec<PathBuf> {
+    let mut total_size = files.iter().map(|(_, len, _)| len).sum::<u64>() + size;
+    files.sort_unstable_by_key(|(modified, ..)| *modified);
+
+    files
+        .into_iter()
+        .take_while(|(_, len, _)| {
+            let evict = total_size > max_size;
+            total_size = total_size.saturating_sub(*len);
+            evict
+        })
+        .map(|(.., path)| path)
+        .collect()
+}
+
+impl BlobCache for FileBlobCache {
+    fn set(&self, key: &[u8], value: &[u8]) {
+        // The file stores the key to detect hash collisions.
+        let size = (4 + key.len() + value.len()) as u64;
+        if size > self.max_size || fs::create_dir_all(&self.directory).is_err() {
+            return;
+        }
+
+        let path = self.path(key);
+        let _ = fs::remove_file(&path);
+        self.evict(size);
+
+        // Write into the temporary file first to never expose partial values. The
+        // name is unique for every writer, since the same key could be stored
+        // concurrently by several threads or processes.
+        let counter = TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let tmp_path = path.with_extension(format!("{}-{counter}.tmp", process::id()));
+        let written = File::create(&tmp_path).and_then(|mut file| {
+            file.write_all(&(key.len() as u32).to_le_bytes())?;
+            file.write_all(key)?;
+            file.write_all(value)
+        });
+
+        if written.and_then(|_| fs::rename(&tmp_path, &path)).is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+    }
+
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let mut data = Vec::new();
+        File::open(self.path(key)).ok()?.read_to_end(&mut data).ok()?;
+
+        let key_len = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+        if data.get(4..4 + key_len)? != key {
+            return None;
+        }
+
+        Some(data.split_off(4 + key_len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The cache in the unique directory, which is removed when dropped.
+    struct TestCache(FileBlobCache);
+
+    impl TestCache {
+        fn new(name: &str, max_size: u64) -> Self {
+            let directory =
+                std::env::temp_dir().join(format!("glutin-blob-cache-{}-{name}", process::id()));
+            let _ = fs::remove_dir_all(&directory);
+            Self(FileBlobCache::new(directory, max_size))
+        }
+    }
+
+    impl Drop for TestCache {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(self.0.directory());
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let cache = TestCache::new("round-trip", 1024);
+
+        assert_eq!(cache.0.get(b"key"), None);
+        cache.0.set(b"key", b"value");
+        assert_eq!(cache.0.get(b"key"), Some(b"value".to_vec()));
+
+        cache.0.set(b"key", b"new value");
+        assert_eq!(cache.0.get(b"key"), Some(b"new value".to_vec()));
+        assert_eq!(cache.0.get(b"other key"), None);
+    }
+
+    #[test]
+    fn too_large_value() {
+        let cache = TestCache::new("too-large-value", 16);
+
+        // The key length and the key take 7 bytes of the file.
+        cache.0.set(b"key", &[0; 10]);
+        assert_eq!(cache.0.get(b"key"), None);
+        cache.0.set(b"key", &[0; 9]);
+        assert_eq!(cache.0.get(b"key"), Some(vec![0; 9]));
+    }
+
+    #[test]
+    fn hash_collision() {
+        let cache = TestCache::new("hash-collision", 1024);
+
+        // Store the value of the `key` at the path of the `other key`, like on
+        // the hash collision.
+        cache.0.set(b"key", b"value");
+        fs::copy(cache.0.path(b"key"), cache.0.path(b"other key")).unwrap();
+
+        assert_eq!(cache.0.get(b"other key"), None);
+        assert_eq!(cache.0.get(b"key"), Some(b"value".to_vec()));
+    }
+
+    #[test]
+    fn eviction() {
+        let cache = TestCache::new("eviction", 64);
+
+        cache.0.set(b"a", &[0; 20]);
+        cache.0.set(b"b", &[1; 40]);
+
+        assert_eq!(cache.0.get(b"a"), None);
+        assert_eq!(cache.0.get(b"b"), Some(vec![1; 40]));
+    }
+
+    #[test]
+    fn eviction_order() {
+        let time = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let files = vec![
+            (time(3), 10, PathBuf::from("c")),
+            (time(1), 10, PathBuf::from("a")),
+            (time(2), 10, PathBuf::from("b")),
+        ];
+
+        assert_eq!(files_to_evict(files.clone(), 0, 30), Vec::<PathBuf>::new());
+        assert_eq!(files_to_evict(files.clone(), 5, 30), vec![PathBuf::from("a")]);
+        assert_eq!(files_to_evict(files.clone(), 15, 30), vec![
+            PathBuf::from("a"),
+            PathBuf::from("b")
+        ]);
+        assert_eq!(files_to_evict(files, 100, 30).len(), 3);
+    }
+}
@@ -237,6 +237,15 @@ impl Config {
         unsafe { self.raw_attribute(egl::NATIVE_VISUAL_ID as EGLint) as u32 }
     }
 
+    /// Whether the config supports the stream producer surfaces, see
+    /// [`Display::create_stream_producer_surface()`].
+    ///
+    /// [`Display::create_stream_producer_surface()`]: super::display::Display::create_stream_producer_surface
+    pub fn supports_stream_surfaces(&self) -> bool {
+        let surface_type = unsafe { self.raw_attribute(egl::SURFACE_TYPE as EGLint) as u32 };
+        surface_type & egl::STREAM_BIT_KHR != 0
+    }
+
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
@@ -191,8 +191,13 @@ impl Display {
                 return Err(super::check_error().err().unwrap());
             }
 
-            let inner =
-                ContextInner { display: self.clone(), config, raw: EglContext(context), api };
+            let inner = ContextInner {
+                display: self.clone(),
+                config,
+                raw: EglContext(context),
+                api,
+                owned: true,
+            };
             Ok(NotCurrentContext::new(inner))
         }
     }
@@ -205,6 +210,42 @@ pub struct NotCurrentContext {
 }
 
 impl NotCurrentContext {
+    /// Wrap the foreign `context` created with the `config`, for example, by
+    /// the other library.
+    ///
+    /// The context is not owned by glutin, thus it's not destroyed when
+    /// dropped.
+    ///
+    /// # Safety
+    ///
+    /// The `context` must be a valid `EGLContext` created with the `config`,
+    /// which is not current and outlives the returned context.
+    pub unsafe fn from_raw(config: &Config, context: EGLContext) -> Result<Self> {
+        let display = config.display();
+
+        let mut api = 0;
+        if unsafe {
+            display.inner.egl.QueryContext(
+                *display.inner.raw,
+                context,
+                egl::CONTEXT_CLIENT_TYPE as EGLint,
+                &mut api,
+            )
+        } == egl::FALSE
+        {
+            return Err(super::check_error().err().unwrap_or_else(|| ErrorKind::BadContext.into()));
+        }
+
+        let inner = ContextInner {
+            display,
+            config: config.clone(),
+            raw: EglContext(context),
+            api: api as EGLenum,
+            owned: false,
+        };
+        Ok(Self::new(inner))
+    }
+
     fn new(inner: ContextInner) -> Self {
         Self { inner }
     }
@@ -358,6 +399,7 @@ pub(crate) struct ContextInner {
     config: Config,
     raw: EglContext,
     api: egl::types::EGLenum,
+    owned: bool,
 }
 
 impl ContextInner {
@@ -431,6 +473,11 @@ impl ContextInner {
         }
     }
 
+    /// Get the raw `EGLContext`.
+    pub(crate) fn raw(&self) -> EGLContext {
+        *self.raw
+    }
+
     /// Query the context attribute.
     fn query_attribute(&self, attribute: EGLint) -> Option<EGLint> {
         unsafe {
@@ -470,6 +517,10 @@ impl ContextInner {
 
 impl Drop for ContextInner {
     fn drop(&mut self) {
+        if !self.owned {
+            return;
+        }
+
         unsafe {
             self.display.inner.egl.DestroyContext(*self.display.inner.raw, *self.raw);
         }
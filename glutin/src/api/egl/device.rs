@@ -2,14 +2,16 @@
 
 use std::collections::HashSet;
 use std::ffi::CStr;
-use std::path::Path;
-use std::ptr;
+use std::path::{Path, PathBuf};
+use std::{fs, ptr};
 
 use glutin_egl_sys::egl;
 use glutin_egl_sys::egl::types::EGLDeviceEXT;
 
 use crate::error::{ErrorKind, Result};
 
+#[cfg(gbm_platform)]
+use super::display::Display;
 use super::display::{extensions_from_ptr, get_extensions, CLIENT_EXTENSIONS};
 use super::{Egl, EGL};
 
@@ -119,6 +121,87 @@ impl Device {
         self.vendor
     }
 
+    /// Whether the device is a software rasterizer, like llvmpipe.
+    ///
+    /// This function will return `false` if the `EGL_MESA_device_software`
+    /// device extension is not available.
+    pub fn is_software(&self) -> bool {
+        self.extensions.contains("EGL_MESA_device_software")
+    }
+
+    /// Get the name of the driver used by the device.
+    ///
+    /// This function will return [`None`] if the
+    /// `EGL_EXT_device_persistent_id` device extension is not available.
+    pub fn driver_name(&self) -> Option<&'static str> {
+        if !self.extensions.contains("EGL_EXT_device_persistent_id") {
+            return None;
+        }
+
+        // SAFETY: We pass a valid EGLDevice pointer, and validated that the enum name
+        // is valid because the extension is present.
+        unsafe { Self::query_string(self.raw_device(), egl::DRIVER_NAME_EXT) }
+    }
+
+    /// Get the UUID of the device, which is persistent across the processes
+    /// and APIs, like Vulkan's `deviceUUID`.
+    ///
+    /// This function will return [`None`] if the
+    /// `EGL_EXT_device_persistent_id` device extension is not available.
+    pub fn device_uuid(&self) -> Option<[u8; egl::UUID_SIZE_EXT]> {
+        self.query_uuid(egl::DEVICE_UUID_EXT)
+    }
+
+    /// Get the UUID of the driver, which is persistent across the processes
+    /// and APIs, like Vulkan's `driverUUID`.
+    ///
+    /// This function will return [`None`] if the
+    /// `EGL_EXT_device_persistent_id` device extension is not available.
+    pub fn driver_uuid(&self) -> Option<[u8; egl::UUID_SIZE_EXT]> {
+        self.query_uuid(egl::DRIVER_UUID_EXT)
+    }
+
+    fn query_uuid(&self, name: egl::types::EGLenum) -> Option<[u8; egl::UUID_SIZE_EXT]> {
+        let egl = super::EGL.as_ref().unwrap();
+        if !self.extensions.contains("EGL_EXT_device_persistent_id")
+            || !egl::QueryDeviceBinaryEXT::is_loaded()
+        {
+            return None;
+        }
+
+        let mut uuid = [0; egl::UUID_SIZE_EXT];
+        let mut size = 0;
+        unsafe {
+            if egl.QueryDeviceBinaryEXT(
+                self.raw_device(),
+                name as _,
+                uuid.len() as _,
+                uuid.as_mut_ptr().cast(),
+                &mut size,
+            ) == egl::FALSE
+            {
+                return None;
+            }
+        }
+
+        (size as usize == uuid.len()).then_some(uuid)
+    }
+
+    /// Create an EGL display on the GBM device for the DRM render node of this
+    /// device.
+    ///
+    /// This function returns [`Err`] if the render node is not available, see
+    /// [`Self::drm_render_device_node_path()`].
+    ///
+    /// See [`Display::from_drm_node()`] for details.
+    #[cfg(gbm_platform)]
+    pub fn open_gbm_display(&self) -> Result<Display> {
+        match self.drm_render_device_node_path() {
+            Some(path) => Display::from_drm_node(path),
+            None => Err(ErrorKind::NotSupported("the device has no DRM render node").into()),
+        }
+    }
+
     /// Get a raw handle to the `EGLDevice`.
     pub fn raw_device(&self) -> EGLDeviceEXT {
         self.inner
@@ -215,3 +298,109 @@ impl Device {
         Ok(Self { inner: ptr, extensions, name, vendor })
     }
 }
+
+/// The helper to pick the [`Device`] out of [`Device::query_devices()`].
+///
+/// Without any criteria the first device reported by EGL is picked.
+#[derive(Default, Debug, Clone)]
+pub struct DeviceSelector {
+    prefer_hardware: bool,
+    drm_node: Option<PathBuf>,
+    vendor: Option<String>,
+}
+
+impl DeviceSelector {
+    /// Create new selector.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Prefer the hardware devices over the software rasterizers.
+    ///
+    /// The device is treated as a software one when it reports the
+    /// `EGL_MESA_device_software` extension or has no DRM device node at all.
+    /// The software devices are still picked when nothing else is available.
+    pub fn prefer_hardware(mut self) -> Self {
+        self.prefer_hardware = true;
+        self
+    }
+
+    /// Only pick the device with the given DRM render or primary node `path`,
+    /// for example, `/dev/dri/renderD128`.
+    ///
+    /// The symbolic links, like the ones in `/dev/dri/by-path`, are resolved
+    /// before the comparison.
+    pub fn with_drm_render_node<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.drm_node = Some(path.into());
+        self
+    }
+
+    /// Only pick the device with the vendor containing the given `vendor`
+    /// string, ignoring the case.
+    ///
+    /// The devices without the `EGL_EXT_device_query_name` extension never
+    /// match.
+    pub fn with_vendor<S: Into<String>>(mut self, vendor: S) -> Self {
+        self.vendor = Some(vendor.into());
+        self
+    }
+
+    /// Pick the best device among the available ones.
+    ///
+    /// This function returns [`Err`] if the devices could not be queried or
+    /// none of them matches the criteria.
+    pub fn select(&self) -> Result<Device> {
+        self.select_from(Device::query_devices()?).ok_or_else(|| ErrorKind::NotFound.into())
+    }
+
+    /// Pick the best device among the given `devices`.
+    pub fn select_from<I: IntoIterator<Item = Device>>(&self, devices: I) -> Option<Device> {
+        let drm_node = self.drm_node.as_deref().map(canonicalize);
+        let vendor = self.vendor.as_ref().map(|vendor| vendor.to_lowercase());
+
+        let mut devices = devices.into_iter().filter(|device| {
+            let matches_drm_node = match drm_node.as_deref() {
+                Some(drm_node) => {
+                    [device.drm_render_device_node_path(), device.drm_device_node_path()]
+                        .into_iter()
+                        .flatten()
+                        .any(|path| canonicalize(path) == drm_node)
+                },
+                None => true,
+            };
+
+            let matches_vendor = match vendor.as_deref() {
+                Some(vendor) => {
+                    device.vendor().is_some_and(|name| name.to_lowercase().contains(vendor))
+                },
+                None => true,
+            };
+
+            matches_drm_node && matches_vendor
+        });
+
+        if !self.prefer_hardware {
+            return devices.next();
+        }
+
+        // Pick the first hardware device falling back to the first software one.
+        let mut software = None;
+        for device in devices {
+            let is_software = device.is_software()
+                || (device.drm_device_node_path().is_none()
+                    && device.drm_render_device_node_path().is_none());
+            if !is_software {
+                return Some(device);
+            }
+
+            software.get_or_insert(device);
+        }
+
+        software
+    }
+}
+
+/// Resolve the symbolic links in the `path`, returning it as is on failure.
+fn canonicalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -5,14 +5,20 @@ use std::ffi::{self, CStr};
 use std::fmt;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
+#[cfg(unix)]
+use std::os::fd::RawFd;
 use std::os::raw::c_char;
+#[cfg(gbm_platform)]
+use std::path::Path;
 use std::sync::Arc;
 
 use glutin_egl_sys::egl;
-use glutin_egl_sys::egl::types::{EGLAttrib, EGLDisplay, EGLint};
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLDisplay, EGLenum, EGLint};
 
 use once_cell::sync::OnceCell;
 
+#[cfg(gbm_platform)]
+use raw_window_handle::GbmDisplayHandle;
 use raw_window_handle::{RawDisplayHandle, XlibDisplayHandle};
 
 use crate::config::ConfigTemplate;
@@ -25,7 +31,9 @@ use crate::surface::{PbufferSurface, PixmapSurface, SurfaceAttributes, WindowSur
 
 use super::config::Config;
 use super::context::NotCurrentContext;
-use super::device::Device;
+use super::device::{Device, DeviceSelector};
+#[cfg(gbm_platform)]
+use super::gbm::GbmDevice;
 use super::surface::Surface;
 
 use super::{Egl, EGL};
@@ -50,25 +58,73 @@ impl Display {
     /// `EGL_DEFAULT_DISPLAY`, which is not recommended or will
     /// work on a platform with a concept of native display, like Wayland.
     pub unsafe fn new(raw_display: RawDisplayHandle) -> Result<Self> {
+        unsafe { Self::new_inner(raw_display, None, &DisplayAttributes::default()) }
+    }
+
+    /// Create EGL display with the native display and the given `attributes`.
+    ///
+    /// The attributes are only used with `eglGetPlatformDisplay` and
+    /// `eglGetPlatformDisplayEXT`, the legacy `eglGetDisplay` fallback ignores
+    /// them.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new()`].
+    pub unsafe fn new_with_attributes(
+        raw_display: RawDisplayHandle,
+        attributes: &DisplayAttributes,
+    ) -> Result<Self> {
+        unsafe { Self::new_inner(raw_display, None, attributes) }
+    }
+
+    /// Create EGL display with the native display, which will render on the
+    /// given `device`.
+    ///
+    /// This is useful on the systems with multiple GPUs, where the driver
+    /// would otherwise pick the device on its own.
+    ///
+    /// This function returns [`Err`] if the `EGL_EXT_explicit_device`
+    /// extension is not available.
+    ///
+    /// # Safety
+    ///
+    /// `raw_display` must point to a valid system display.
+    pub unsafe fn new_on_device(raw_display: RawDisplayHandle, device: &Device) -> Result<Self> {
+        unsafe { Self::new_inner(raw_display, Some(device), &DisplayAttributes::default()) }
+    }
+
+    unsafe fn new_inner(
+        raw_display: RawDisplayHandle,
+        device: Option<&Device>,
+        attributes: &DisplayAttributes,
+    ) -> Result<Self> {
         let egl = match EGL.as_ref() {
             Some(egl) => egl,
             None => return Err(ErrorKind::NotFound.into()),
         };
 
-        CLIENT_EXTENSIONS.get_or_init(|| get_extensions(egl, egl::NO_DISPLAY));
+        let extensions = CLIENT_EXTENSIONS.get_or_init(|| get_extensions(egl, egl::NO_DISPLAY));
+
+        if device.is_some() && !extensions.contains("EGL_EXT_explicit_device") {
+            return Err(ErrorKind::NotSupported(
+                "creating a platform display for the device is not supported",
+            )
+            .into());
+        }
 
         // Create a EGL display by chaining all display creation functions aborting on
         // `EGL_BAD_ATTRIBUTE`.
-        let display = Self::get_platform_display(egl, raw_display)
+        let display = Self::get_platform_display(egl, raw_display, device, attributes)
             .or_else(|err| {
                 if err.error_kind() == ErrorKind::BadAttribute {
                     Err(err)
                 } else {
-                    Self::get_platform_display_ext(egl, raw_display)
+                    Self::get_platform_display_ext(egl, raw_display, device, attributes)
                 }
             })
             .or_else(|err| {
-                if err.error_kind() == ErrorKind::BadAttribute {
+                // The legacy display can't be pinned to the device.
+                if err.error_kind() == ErrorKind::BadAttribute || device.is_some() {
                     Err(err)
                 } else {
                     Self::get_display(egl, raw_display)
@@ -96,6 +152,29 @@ impl Display {
     pub unsafe fn with_device(
         device: &Device,
         raw_display: Option<RawDisplayHandle>,
+    ) -> Result<Self> {
+        unsafe {
+            Self::with_device_and_attributes(device, raw_display, &DisplayAttributes::default())
+        }
+    }
+
+    /// Create an EGL display using the specified device and the given
+    /// `attributes`.
+    ///
+    /// The DRM master fd from the `attributes` takes precedence over the one
+    /// from the `raw_display`.
+    ///
+    /// This function returns [`Err`] if the DRM master fd is provided, but the
+    /// device doesn't support the `EGL_EXT_device_drm` extension, or when the
+    /// raw attribute value doesn't fit into `EGLint`.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::with_device()`].
+    pub unsafe fn with_device_and_attributes(
+        device: &Device,
+        raw_display: Option<RawDisplayHandle>,
+        attributes: &DisplayAttributes,
     ) -> Result<Self> {
         let egl = match EGL.as_ref() {
             Some(egl) => egl,
@@ -120,15 +199,10 @@ impl Display {
             .into());
         }
 
-        let mut attrs = Vec::<EGLint>::with_capacity(3);
+        let mut attrs = Vec::<EGLint>::with_capacity(3 + attributes.raw.len() * 2);
 
-        match raw_display {
-            Some(RawDisplayHandle::Drm(handle))
-                if device.extensions().contains("EGL_EXT_device_drm") =>
-            {
-                attrs.push(egl::DRM_MASTER_FD_EXT as EGLint);
-                attrs.push(handle.fd as EGLint);
-            },
+        let drm_master_fd = match raw_display {
+            Some(RawDisplayHandle::Drm(handle)) => Some(handle.fd),
             Some(_) => {
                 return Err(ErrorKind::NotSupported(
                     "`egl::display::Display::with_device()` does not support \
@@ -136,9 +210,26 @@ impl Display {
                 )
                 .into())
             },
-            None => {},
+            None => None,
         };
 
+        #[cfg(unix)]
+        let drm_master_fd = attributes.drm_master_fd.or(drm_master_fd);
+
+        if let Some(fd) = drm_master_fd {
+            if !device.extensions().contains("EGL_EXT_device_drm") {
+                return Err(ErrorKind::NotSupported(
+                    "the DRM master fd requires `EGL_EXT_device_drm` on the device",
+                )
+                .into());
+            }
+
+            attrs.push(egl::DRM_MASTER_FD_EXT as EGLint);
+            attrs.push(fd as EGLint);
+        }
+
+        push_raw_attributes(&mut attrs, &attributes.raw)?;
+
         // Push at the end so we can pop it on failure
         let mut has_display_reference = extensions.contains("EGL_KHR_display_reference");
         if has_display_reference {
@@ -180,6 +271,155 @@ impl Display {
         Self::initialize_display(egl, platform_display, None)
     }
 
+    /// Create an EGL display using the device picked by the `selector`.
+    ///
+    /// This is the same as calling [`Display::with_device()`] without the
+    /// native display on the result of [`DeviceSelector::select()`].
+    pub fn with_device_selector(selector: &DeviceSelector) -> Result<Self> {
+        let device = selector.select()?;
+        unsafe { Self::with_device(&device, None) }
+    }
+
+    /// Create an EGL display on the GBM device created for the DRM node at
+    /// `path`, for example, `/dev/dri/renderD128`.
+    ///
+    /// The node and the GBM device are owned by the display and closed once
+    /// it's dropped, which will also terminate the display.
+    #[cfg(gbm_platform)]
+    pub fn from_drm_node<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let gbm_device = GbmDevice::open(path.as_ref())?;
+
+        let raw_display = RawDisplayHandle::Gbm(GbmDisplayHandle::new(gbm_device.raw()));
+        let mut display = unsafe { Self::new(raw_display)? };
+
+        // The display was just created, so we're the only owner.
+        Arc::get_mut(&mut display.inner).unwrap().gbm_device = Some(gbm_device);
+
+        Ok(display)
+    }
+
+    /// Create an EGL display without any native display or device.
+    ///
+    /// The display could only be used to render off screen, for example, into
+    /// the pbuffer surfaces or with [`make_current_surfaceless`]. Which device
+    /// will be used for rendering is up to the implementation, which may end
+    /// up using the software rasterizer.
+    ///
+    /// This function returns [`Err`] if the `EGL_MESA_platform_surfaceless`
+    /// extension is not available.
+    ///
+    /// [`make_current_surfaceless`]: crate::context::NotCurrentGlContext::make_current_surfaceless
+    pub fn surfaceless() -> Result<Self> {
+        let egl = match EGL.as_ref() {
+            Some(egl) => egl,
+            None => return Err(ErrorKind::NotFound.into()),
+        };
+
+        let extensions = CLIENT_EXTENSIONS.get_or_init(|| get_extensions(egl, egl::NO_DISPLAY));
+
+        if !egl.GetPlatformDisplayEXT.is_loaded()
+            || !extensions.contains("EGL_MESA_platform_surfaceless")
+        {
+            return Err(
+                ErrorKind::NotSupported("surfaceless platform display is not supported").into()
+            );
+        }
+
+        let mut attrs = Vec::<EGLint>::with_capacity(3);
+
+        // Push at the end so we can pop it on failure
+        let mut has_display_reference = extensions.contains("EGL_KHR_display_reference");
+        if has_display_reference {
+            attrs.push(egl::TRACK_REFERENCES_KHR as _);
+            attrs.push(egl::TRUE as _);
+        }
+
+        // Push `egl::NONE` to terminate the list.
+        attrs.push(egl::NONE as EGLint);
+
+        // NOTE: See the comment in `Display::with_device()` for the reasoning behind
+        // the fallback.
+        let platform_display = loop {
+            match Self::check_display_error(unsafe {
+                egl.GetPlatformDisplayEXT(
+                    egl::PLATFORM_SURFACELESS_MESA,
+                    egl::DEFAULT_DISPLAY as *mut _,
+                    attrs.as_ptr(),
+                )
+            }) {
+                Err(_) if has_display_reference => {
+                    attrs.pop();
+                    attrs.pop();
+                    attrs.pop();
+                    attrs.push(egl::NONE as EGLint);
+                    has_display_reference = false;
+                },
+                platform_display => break platform_display,
+            }
+        }
+        .map(EglDisplay::Ext)?;
+
+        Self::initialize_display(egl, platform_display, None)
+    }
+
+    /// Create EGL display from the already initialized raw `EGLDisplay`.
+    ///
+    /// The version, extensions and features are loaded from the `display`,
+    /// which is not owned by glutin, thus it's never terminated when dropped.
+    ///
+    /// The display will use the legacy surface creation functions, since it's
+    /// not known how the `display` was created.
+    ///
+    /// # Safety
+    ///
+    /// The `display` must be a valid and initialized `EGLDisplay`, which stays
+    /// initialized while the returned display and all the objects created
+    /// from it are alive.
+    pub unsafe fn from_raw(display: EGLDisplay) -> Result<Self> {
+        let egl = match EGL.as_ref() {
+            Some(egl) => egl,
+            None => return Err(ErrorKind::NotFound.into()),
+        };
+
+        CLIENT_EXTENSIONS.get_or_init(|| get_extensions(egl, egl::NO_DISPLAY));
+
+        if display == egl::NO_DISPLAY {
+            return Err(ErrorKind::BadDisplay.into());
+        }
+
+        let version = unsafe {
+            let version = egl.QueryString(display, egl::VERSION as EGLint);
+            if version.is_null() {
+                return Err(super::check_error()
+                    .err()
+                    .unwrap_or_else(|| ErrorKind::BadDisplay.into()));
+            }
+
+            CStr::from_ptr(version)
+                .to_str()
+                .ok()
+                .and_then(parse_version)
+                .ok_or(ErrorKind::NotSupported("failed to parse the version of EGLDisplay"))?
+        };
+
+        let display = EglDisplay::Legacy(display);
+        let display_extensions = get_extensions(egl, *display);
+        let features = Self::extract_display_features(&display_extensions, version);
+
+        let inner = Arc::new(DisplayInner {
+            egl,
+            raw: display,
+            _native_display: None,
+            version,
+            display_extensions,
+            features,
+            owned: false,
+            #[cfg(gbm_platform)]
+            gbm_device: None,
+        });
+        Ok(Self { inner })
+    }
+
     /// Get the [`Device`] the display is using.
     ///
     /// This function returns [`Err`] if the `EGL_EXT_device_query` or
@@ -227,6 +467,51 @@ impl Display {
         Device::from_ptr(self.inner.egl, device)
     }
 
+    /// Get the name of the driver used by the display, for example, `iris` or
+    /// `llvmpipe`.
+    ///
+    /// This function returns [`None`] if the `EGL_MESA_query_driver` extension
+    /// is not available.
+    pub fn driver_name(&self) -> Option<&str> {
+        if !self.inner.display_extensions.contains("EGL_MESA_query_driver") {
+            return None;
+        }
+
+        let ptr = unsafe { self.inner.egl.GetDisplayDriverName(*self.inner.raw) };
+        if ptr.is_null() {
+            return None;
+        }
+
+        unsafe { CStr::from_ptr(ptr) }.to_str().ok()
+    }
+
+    /// Get the XML describing the configuration options of the driver used by
+    /// the display along with their current values.
+    ///
+    /// This function returns [`None`] if the `EGL_MESA_query_driver` extension
+    /// is not available.
+    #[cfg(unix)]
+    pub fn driver_config(&self) -> Option<String> {
+        extern "C" {
+            fn free(ptr: *mut ffi::c_void);
+        }
+
+        if !self.inner.display_extensions.contains("EGL_MESA_query_driver") {
+            return None;
+        }
+
+        let ptr = unsafe { self.inner.egl.GetDisplayDriverConfig(*self.inner.raw) };
+        if ptr.is_null() {
+            return None;
+        }
+
+        // The string is allocated by the driver and must be freed by the caller.
+        let config = unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned();
+        unsafe { free(ptr.cast()) };
+
+        Some(config)
+    }
+
     /// Get a reference to the initialized EGL API.
     pub fn egl(&self) -> &'static Egl {
         self.inner.egl
@@ -251,14 +536,19 @@ impl Display {
         }
     }
 
-    fn get_platform_display(egl: &Egl, display: RawDisplayHandle) -> Result<EglDisplay> {
+    fn get_platform_display(
+        egl: &Egl,
+        display: RawDisplayHandle,
+        device: Option<&Device>,
+        attributes: &DisplayAttributes,
+    ) -> Result<EglDisplay> {
         if !egl.GetPlatformDisplay.is_loaded() {
             return Err(ErrorKind::NotSupported("eglGetPlatformDisplay is not supported").into());
         }
 
         let extensions = CLIENT_EXTENSIONS.get().unwrap();
 
-        let mut attrs = Vec::<EGLAttrib>::with_capacity(5);
+        let mut attrs = Vec::<EGLAttrib>::with_capacity(7 + attributes.raw.len() * 2);
         let (platform, display) = match display {
             RawDisplayHandle::Wayland(handle)
                 if extensions.contains("EGL_KHR_platform_wayland") =>
@@ -267,7 +557,7 @@ impl Display {
             },
             RawDisplayHandle::Xlib(handle) if extensions.contains("EGL_KHR_platform_x11") => {
                 attrs.push(egl::PLATFORM_X11_SCREEN_KHR as EGLAttrib);
-                attrs.push(handle.screen as EGLAttrib);
+                attrs.push(attributes.x11_screen.unwrap_or(handle.screen) as EGLAttrib);
                 (
                     egl::PLATFORM_X11_KHR,
                     handle.display.map_or(egl::DEFAULT_DISPLAY as *mut _, |d| d.as_ptr()),
@@ -292,6 +582,16 @@ impl Display {
             },
         };
 
+        if let Some(device) = device {
+            attrs.push(egl::DEVICE_EXT as EGLAttrib);
+            attrs.push(device.raw_device() as EGLAttrib);
+        }
+
+        for &(name, value) in &attributes.raw {
+            attrs.push(name as EGLAttrib);
+            attrs.push(value);
+        }
+
         // Push at the end so we can pop it on failure
         let mut has_display_reference = extensions.contains("EGL_KHR_display_reference");
         if has_display_reference {
@@ -328,14 +628,19 @@ impl Display {
         platform_display.map(EglDisplay::Khr)
     }
 
-    fn get_platform_display_ext(egl: &Egl, display: RawDisplayHandle) -> Result<EglDisplay> {
+    fn get_platform_display_ext(
+        egl: &Egl,
+        display: RawDisplayHandle,
+        device: Option<&Device>,
+        attributes: &DisplayAttributes,
+    ) -> Result<EglDisplay> {
         if !egl.GetPlatformDisplayEXT.is_loaded() {
             return Err(ErrorKind::NotSupported("eglGetPlatformDisplayEXT is not supported").into());
         }
 
         let extensions = CLIENT_EXTENSIONS.get().unwrap();
 
-        let mut attrs = Vec::<EGLint>::with_capacity(5);
+        let mut attrs = Vec::<EGLint>::with_capacity(9 + attributes.raw.len() * 2);
         let mut legacy = false;
         let (platform, display) = match display {
             RawDisplayHandle::Wayland(handle)
@@ -345,7 +650,7 @@ impl Display {
             },
             RawDisplayHandle::Xlib(handle) if extensions.contains("EGL_EXT_platform_x11") => {
                 attrs.push(egl::PLATFORM_X11_SCREEN_EXT as EGLint);
-                attrs.push(handle.screen as EGLint);
+                attrs.push(attributes.x11_screen.unwrap_or(handle.screen) as EGLint);
                 (
                     egl::PLATFORM_X11_EXT,
                     handle.display.map_or(egl::DEFAULT_DISPLAY as *mut _, |d| d.as_ptr()),
@@ -356,7 +661,7 @@ impl Display {
                     || extensions.contains("EGL_EXT_platform_xcb") =>
             {
                 attrs.push(egl::PLATFORM_XCB_SCREEN_EXT as EGLint);
-                attrs.push(handle.screen as EGLint);
+                attrs.push(attributes.x11_screen.unwrap_or(handle.screen) as EGLint);
                 (
                     egl::PLATFORM_XCB_EXT,
                     handle.connection.map_or(egl::DEFAULT_DISPLAY as *mut _, |c| c.as_ptr()),
@@ -383,6 +688,17 @@ impl Display {
             RawDisplayHandle::Windows(..) if extensions.contains("EGL_ANGLE_platform_angle") => {
                 // Only CreateWindowSurface appears to work with Angle.
                 legacy = true;
+
+                if let Some(platform_type) = attributes.angle_platform_type {
+                    attrs.push(egl::PLATFORM_ANGLE_TYPE_ANGLE as EGLint);
+                    attrs.push(platform_type.raw() as EGLint);
+                }
+
+                if let Some(device_type) = attributes.angle_device_type {
+                    attrs.push(egl::PLATFORM_ANGLE_DEVICE_TYPE_ANGLE as EGLint);
+                    attrs.push(device_type.raw() as EGLint);
+                }
+
                 (egl::PLATFORM_ANGLE_ANGLE, egl::DEFAULT_DISPLAY as *mut _)
             },
             _ => {
@@ -392,6 +708,18 @@ impl Display {
             },
         };
 
+        if let Some(device) = device {
+            // The device handle must fit into `EGLint` here, which is usually not the case
+            // on 64-bit systems, where `eglGetPlatformDisplay` must be used instead.
+            let device = EGLint::try_from(device.raw_device() as isize).map_err(|_| {
+                ErrorKind::NotSupported("the device handle doesn't fit into EGLint attribute")
+            })?;
+            attrs.push(egl::DEVICE_EXT as EGLint);
+            attrs.push(device);
+        }
+
+        push_raw_attributes(&mut attrs, &attributes.raw)?;
+
         // Push at the end so we can pop it on failure
         let mut has_display_reference = extensions.contains("EGL_KHR_display_reference");
         if has_display_reference {
@@ -492,6 +820,15 @@ impl Display {
             extensions.contains("EGL_KHR_create_context_no_error"),
         );
 
+        supported_features
+            .set(DisplayFeatures::PARTIAL_UPDATE, extensions.contains("EGL_KHR_partial_update"));
+
+        supported_features.set(
+            DisplayFeatures::SWAP_BUFFERS_WITH_DAMAGE,
+            extensions.contains("EGL_KHR_swap_buffers_with_damage")
+                || extensions.contains("EGL_EXT_swap_buffers_with_damage"),
+        );
+
         supported_features
     }
 
@@ -552,6 +889,9 @@ impl Display {
             version,
             display_extensions,
             features,
+            owned: true,
+            #[cfg(gbm_platform)]
+            gbm_device: None,
         });
         Ok(Self { inner })
     }
@@ -630,6 +970,145 @@ impl AsRawDisplay for Display {
 
 impl Sealed for Display {}
 
+/// The builder to help customizing the display creation.
+#[derive(Default, Debug, Clone)]
+pub struct DisplayAttributesBuilder {
+    attributes: DisplayAttributes,
+}
+
+impl DisplayAttributesBuilder {
+    /// Create new builder.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Override the X11 screen of the native display.
+    ///
+    /// By default the screen from the [`RawDisplayHandle`] is used.
+    pub fn with_x11_screen(mut self, screen: i32) -> Self {
+        self.attributes.x11_screen = Some(screen);
+        self
+    }
+
+    /// Set the ANGLE backend, which is used when the display is created with
+    /// the `EGL_ANGLE_platform_angle` extension.
+    ///
+    /// By default ANGLE picks the backend on its own.
+    pub fn with_angle_platform_type(mut self, platform_type: AnglePlatformType) -> Self {
+        self.attributes.angle_platform_type = Some(platform_type);
+        self
+    }
+
+    /// Set the ANGLE device type, which is used when the display is created
+    /// with the `EGL_ANGLE_platform_angle` extension.
+    ///
+    /// By default the hardware device is used.
+    pub fn with_angle_device_type(mut self, device_type: AngleDeviceType) -> Self {
+        self.attributes.angle_device_type = Some(device_type);
+        self
+    }
+
+    /// Set the DRM master fd used with the `EGL_EXT_device_drm` extension by
+    /// [`Display::with_device_and_attributes()`].
+    ///
+    /// The `fd` may be closed after the display was created.
+    #[cfg(unix)]
+    pub fn with_drm_master_fd(mut self, fd: RawFd) -> Self {
+        self.attributes.drm_master_fd = Some(fd);
+        self
+    }
+
+    /// Pass the raw attribute `name` with the `value` to the display creation.
+    ///
+    /// The attribute is passed as is, so it's up to the caller to ensure that
+    /// the implementation supports it. When the display is created with the
+    /// `EXT` functions, the `value` must fit into `EGLint`.
+    pub fn with_raw_attribute(mut self, name: EGLenum, value: EGLAttrib) -> Self {
+        self.attributes.raw.push((name, value));
+        self
+    }
+
+    /// Build the display attributes.
+    pub fn build(self) -> DisplayAttributes {
+        self.attributes
+    }
+}
+
+/// The attributes that are used to create the display.
+#[derive(Default, Debug, Clone)]
+pub struct DisplayAttributes {
+    pub(crate) x11_screen: Option<i32>,
+
+    pub(crate) angle_platform_type: Option<AnglePlatformType>,
+
+    pub(crate) angle_device_type: Option<AngleDeviceType>,
+
+    #[cfg(unix)]
+    pub(crate) drm_master_fd: Option<RawFd>,
+
+    pub(crate) raw: Vec<(EGLenum, EGLAttrib)>,
+}
+
+/// The backend used by ANGLE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnglePlatformType {
+    /// Let ANGLE pick the backend.
+    Default,
+    /// The Direct3D 9 backend.
+    D3D9,
+    /// The Direct3D 11 backend.
+    D3D11,
+    /// The desktop OpenGL backend.
+    OpenGl,
+    /// The OpenGL ES backend.
+    OpenGlEs,
+    /// The Vulkan backend.
+    Vulkan,
+    /// The Metal backend.
+    Metal,
+}
+
+impl AnglePlatformType {
+    fn raw(self) -> EGLenum {
+        match self {
+            Self::Default => egl::PLATFORM_ANGLE_TYPE_DEFAULT_ANGLE,
+            Self::D3D9 => egl::PLATFORM_ANGLE_TYPE_D3D9_ANGLE,
+            Self::D3D11 => egl::PLATFORM_ANGLE_TYPE_D3D11_ANGLE,
+            Self::OpenGl => egl::PLATFORM_ANGLE_TYPE_OPENGL_ANGLE,
+            Self::OpenGlEs => egl::PLATFORM_ANGLE_TYPE_OPENGLES_ANGLE,
+            Self::Vulkan => egl::PLATFORM_ANGLE_TYPE_VULKAN_ANGLE,
+            Self::Metal => egl::PLATFORM_ANGLE_TYPE_METAL_ANGLE,
+        }
+    }
+}
+
+/// The device used by the ANGLE backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AngleDeviceType {
+    /// The hardware device.
+    Hardware,
+    /// The Direct3D WARP software device.
+    D3DWarp,
+    /// The Direct3D reference device.
+    D3DReference,
+    /// The SwiftShader software device.
+    SwiftShader,
+    /// The null device, which doesn't render anything.
+    Null,
+}
+
+impl AngleDeviceType {
+    fn raw(self) -> EGLenum {
+        match self {
+            Self::Hardware => egl::PLATFORM_ANGLE_DEVICE_TYPE_HARDWARE_ANGLE,
+            Self::D3DWarp => egl::PLATFORM_ANGLE_DEVICE_TYPE_D3D_WARP_ANGLE,
+            Self::D3DReference => egl::PLATFORM_ANGLE_DEVICE_TYPE_D3D_REFERENCE_ANGLE,
+            Self::SwiftShader => egl::PLATFORM_ANGLE_DEVICE_TYPE_SWIFTSHADER_ANGLE,
+            Self::Null => egl::PLATFORM_ANGLE_DEVICE_TYPE_NULL_ANGLE,
+        }
+    }
+}
+
 pub(crate) struct DisplayInner {
     /// Pointer to the EGL handler to simplify API calls.
     pub(crate) egl: &'static Egl,
@@ -648,6 +1127,13 @@ pub(crate) struct DisplayInner {
 
     /// The raw display used to create EGL display.
     pub(crate) _native_display: Option<NativeDisplay>,
+
+    /// Whether the display was created by glutin and could be terminated.
+    pub(crate) owned: bool,
+
+    /// The GBM device owned by the display.
+    #[cfg(gbm_platform)]
+    pub(crate) gbm_device: Option<GbmDevice>,
 }
 
 impl DisplayInner {
@@ -694,7 +1180,14 @@ impl fmt::Debug for DisplayInner {
 
 impl Drop for DisplayInner {
     fn drop(&mut self) {
-        if self.uses_display_reference() {
+        // Nobody else could use the display created from the GBM device we own, so it
+        // must be terminated before the device is destroyed.
+        #[cfg(gbm_platform)]
+        let owns_native_display = self.gbm_device.is_some();
+        #[cfg(not(gbm_platform))]
+        let owns_native_display = false;
+
+        if self.owned && (owns_native_display || self.uses_display_reference()) {
             unsafe {
                 self.egl.Terminate(*self.raw);
             }
@@ -787,6 +1280,28 @@ impl Deref for EglDisplay {
     }
 }
 
+/// Append the `raw` attributes to the `EGLint` attributes list.
+///
+/// Returns [`Err`] when any of the values doesn't fit into `EGLint`.
+fn push_raw_attributes(attrs: &mut Vec<EGLint>, raw: &[(EGLenum, EGLAttrib)]) -> Result<()> {
+    for &(name, value) in raw {
+        let value = EGLint::try_from(value).map_err(|_| {
+            ErrorKind::NotSupported("the raw attribute value doesn't fit into EGLint attribute")
+        })?;
+        attrs.push(name as EGLint);
+        attrs.push(value);
+    }
+
+    Ok(())
+}
+
+/// Parse the `major.minor` version from the EGL version string.
+fn parse_version(version: &str) -> Option<Version> {
+    let version = version.split(' ').next()?;
+    let (major, minor) = version.split_once('.')?;
+    Some(Version::new(major.parse().ok()?, minor.parse().ok()?))
+}
+
 /// Collect EGL extensions for the given `display`.
 pub(crate) fn get_extensions(egl: &Egl, display: EGLDisplay) -> HashSet<&'static str> {
     unsafe {
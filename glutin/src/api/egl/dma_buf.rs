@@ -0,0 +1,402 @@
+//! Everything related to the Linux dma-buf interop.
+
+use std::num::NonZeroU32;
+use std::os::fd::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
+use std::ptr;
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLenum, EGLint};
+
+use crate::error::{ErrorKind, Result};
+
+use super::display::Display;
+use super::image::Image;
+
+/// The maximum number of planes a dma-buf could have.
+pub const MAX_DMA_BUF_PLANES: usize = 4;
+
+/// The per plane dma-buf attributes.
+const PLANE_ATTRIBUTES: [[EGLenum; 5]; MAX_DMA_BUF_PLANES] = [
+    [
+        egl::DMA_BUF_PLANE0_FD_EXT,
+        egl::DMA_BUF_PLANE0_OFFSET_EXT,
+        egl::DMA_BUF_PLANE0_PITCH_EXT,
+        egl::DMA_BUF_PLANE0_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE0_MODIFIER_HI_EXT,
+    ],
+    [
+        egl::DMA_BUF_PLANE1_FD_EXT,
+        egl::DMA_BUF_PLANE1_OFFSET_EXT,
+        egl::DMA_BUF_PLANE1_PITCH_EXT,
+        egl::DMA_BUF_PLANE1_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE1_MODIFIER_HI_EXT,
+    ],
+    [
+        egl::DMA_BUF_PLANE2_FD_EXT,
+        egl::DMA_BUF_PLANE2_OFFSET_EXT,
+        egl::DMA_BUF_PLANE2_PITCH_EXT,
+        egl::DMA_BUF_PLANE2_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE2_MODIFIER_HI_EXT,
+    ],
+    [
+        egl::DMA_BUF_PLANE3_FD_EXT,
+        egl::DMA_BUF_PLANE3_OFFSET_EXT,
+        egl::DMA_BUF_PLANE3_PITCH_EXT,
+        egl::DMA_BUF_PLANE3_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE3_MODIFIER_HI_EXT,
+    ],
+];
+
+impl Display {
+    /// Import the multi-plane dma-buf described by `dma_buf` as an [`Image`].
+    ///
+    /// The file descriptors of the planes are not consumed, EGL holds its own
+    /// references to the underlying buffers, so they could be closed right
+    /// after the import.
+    ///
+    /// This function returns [`Err`] if the `EGL_EXT_image_dma_buf_import`
+    /// extension is not available, or when the explicit modifiers are passed
+    /// without the `EGL_EXT_image_dma_buf_import_modifiers` extension.
+    pub fn import_dma_buf(&self, dma_buf: &DmaBufImage<'_>) -> Result<Image> {
+        if !self.inner.display_extensions.contains("EGL_EXT_image_dma_buf_import") {
+            return Err(ErrorKind::NotSupported("dma-buf import is not supported").into());
+        }
+
+        let planes = dma_buf.planes;
+        if planes.is_empty() || planes.len() > MAX_DMA_BUF_PLANES {
+            return Err(ErrorKind::BadParameter.into());
+        }
+
+        // The 4th plane was added alongside the modifiers.
+        let has_modifiers = planes.iter().any(|plane| plane.modifier.is_some());
+        if has_modifiers || planes.len() == MAX_DMA_BUF_PLANES {
+            self.check_dma_buf_modifiers()?;
+        }
+
+        let mut attrs = Vec::<EGLAttrib>::with_capacity(10 + planes.len() * 10);
+
+        attrs.push(egl::WIDTH as EGLAttrib);
+        attrs.push(dma_buf.width.get() as EGLAttrib);
+        attrs.push(egl::HEIGHT as EGLAttrib);
+        attrs.push(dma_buf.height.get() as EGLAttrib);
+        attrs.push(egl::LINUX_DRM_FOURCC_EXT as EGLAttrib);
+        attrs.push(dma_buf.fourcc as EGLAttrib);
+
+        for (plane, [fd, offset, pitch, modifier_lo, modifier_hi]) in
+            planes.iter().zip(PLANE_ATTRIBUTES)
+        {
+            attrs.push(fd as EGLAttrib);
+            attrs.push(plane.fd.as_raw_fd() as EGLAttrib);
+            attrs.push(offset as EGLAttrib);
+            attrs.push(plane.offset as EGLAttrib);
+            attrs.push(pitch as EGLAttrib);
+            attrs.push(plane.stride as EGLAttrib);
+
+            if let Some(modifier) = plane.modifier {
+                attrs.push(modifier_lo as EGLAttrib);
+                attrs.push((modifier & 0xffff_ffff) as EGLAttrib);
+                attrs.push(modifier_hi as EGLAttrib);
+                attrs.push((modifier >> 32) as EGLAttrib);
+            }
+        }
+
+        if let Some(color_space) = dma_buf.color_space {
+            attrs.push(egl::YUV_COLOR_SPACE_HINT_EXT as EGLAttrib);
+            attrs.push(color_space.raw() as EGLAttrib);
+        }
+
+        if let Some(sample_range) = dma_buf.sample_range {
+            attrs.push(egl::SAMPLE_RANGE_HINT_EXT as EGLAttrib);
+            attrs.push(sample_range.raw() as EGLAttrib);
+        }
+
+        unsafe {
+            self.create_image_raw(egl::NO_CONTEXT, egl::LINUX_DMA_BUF_EXT, ptr::null_mut(), &attrs)
+        }
+    }
+
+    /// Query the DRM fourcc formats supported for the dma-buf import.
+    ///
+    /// This function returns [`Err`] if the
+    /// `EGL_EXT_image_dma_buf_import_modifiers` extension is not available.
+    pub fn dma_buf_formats(&self) -> Result<Vec<u32>> {
+        self.check_dma_buf_modifiers()?;
+
+        let display = &self.inner;
+        unsafe {
+            let mut num_formats = 0;
+            if display.egl.QueryDmaBufFormatsEXT(*display.raw, 0, ptr::null_mut(), &mut num_formats)
+                == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            let mut formats = vec![0; num_formats as usize];
+            if display.egl.QueryDmaBufFormatsEXT(
+                *display.raw,
+                num_formats,
+                formats.as_mut_ptr(),
+                &mut num_formats,
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            formats.truncate(num_formats as usize);
+            Ok(formats.into_iter().map(|format| format as u32).collect())
+        }
+    }
+
+    /// Query the DRM format modifiers supported for the dma-buf import with
+    /// the given `fourcc` format.
+    ///
+    /// This function returns [`Err`] if the
+    /// `EGL_EXT_image_dma_buf_import_modifiers` extension is not available.
+    pub fn dma_buf_modifiers(&self, fourcc: u32) -> Result<Vec<DmaBufModifier>> {
+        self.check_dma_buf_modifiers()?;
+
+        let display = &self.inner;
+        let fourcc = fourcc as EGLint;
+        unsafe {
+            let mut num_modifiers = 0;
+            if display.egl.QueryDmaBufModifiersEXT(
+                *display.raw,
+                fourcc,
+                0,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                &mut num_modifiers,
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            let mut modifiers = vec![0; num_modifiers as usize];
+            let mut external_only = vec![egl::FALSE; num_modifiers as usize];
+            if display.egl.QueryDmaBufModifiersEXT(
+                *display.raw,
+                fourcc,
+                num_modifiers,
+                modifiers.as_mut_ptr(),
+                external_only.as_mut_ptr(),
+                &mut num_modifiers,
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            modifiers.truncate(num_modifiers as usize);
+            Ok(modifiers
+                .into_iter()
+                .zip(external_only)
+                .map(|(modifier, external_only)| DmaBufModifier {
+                    modifier,
+                    external_only: external_only != egl::FALSE,
+                })
+                .collect())
+        }
+    }
+
+    /// Export the `image` as a dma-buf.
+    ///
+    /// The `image` could be created on any context of the display, for
+    /// example, with [`Display::create_image()`], and the commands rendering
+    /// into it must be flushed before handing the dma-buf to the consumer.
+    ///
+    /// This function returns [`Err`] if the `EGL_MESA_image_dma_buf_export`
+    /// extension is not available.
+    pub fn export_dma_buf(&self, image: &Image) -> Result<ExportedDmaBuf> {
+        let display = &self.inner;
+        if !display.display_extensions.contains("EGL_MESA_image_dma_buf_export") {
+            return Err(ErrorKind::NotSupported("dma-buf export is not supported").into());
+        }
+
+        let mut fourcc = 0;
+        let mut num_planes = 0;
+        let mut modifiers = [0; MAX_DMA_BUF_PLANES];
+        unsafe {
+            if display.egl.ExportDMABUFImageQueryMESA(
+                *display.raw,
+                image.raw_image(),
+                &mut fourcc,
+                &mut num_planes,
+                modifiers.as_mut_ptr(),
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+        }
+
+        let num_planes = num_planes as usize;
+        if num_planes == 0 || num_planes > MAX_DMA_BUF_PLANES {
+            return Err(ErrorKind::BadMatch.into());
+        }
+
+        let mut fds = [-1; MAX_DMA_BUF_PLANES];
+        let mut strides = [0; MAX_DMA_BUF_PLANES];
+        let mut offsets = [0; MAX_DMA_BUF_PLANES];
+        unsafe {
+            if display.egl.ExportDMABUFImageMESA(
+                *display.raw,
+                image.raw_image(),
+                fds.as_mut_ptr(),
+                strides.as_mut_ptr(),
+                offsets.as_mut_ptr(),
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+        }
+
+        // SAFETY: The fds were created by EGL, so we own them.
+        let owned_fds: Vec<Option<OwnedFd>> = fds[..num_planes]
+            .iter()
+            .map(|&fd| (fd >= 0).then(|| unsafe { OwnedFd::from_raw_fd(fd) }))
+            .collect();
+
+        // The planes sharing the buffer with the previous one may not get their
+        // own fd, so duplicate it to give every plane an owned one.
+        let mut planes = Vec::<ExportedDmaBufPlane>::with_capacity(num_planes);
+        for (index, fd) in owned_fds.into_iter().enumerate() {
+            let fd = match (fd, planes.last()) {
+                (Some(fd), _) => fd,
+                (None, Some(previous)) => {
+                    previous.fd.try_clone().map_err(|_| ErrorKind::BadAccess)?
+                },
+                (None, None) => return Err(ErrorKind::BadMatch.into()),
+            };
+
+            planes.push(ExportedDmaBufPlane {
+                fd,
+                offset: offsets[index] as u32,
+                stride: strides[index] as u32,
+            });
+        }
+
+        Ok(ExportedDmaBuf { fourcc: fourcc as u32, modifier: modifiers[0], planes })
+    }
+
+    fn check_dma_buf_modifiers(&self) -> Result<()> {
+        if self.inner.display_extensions.contains("EGL_EXT_image_dma_buf_import_modifiers") {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("dma-buf modifiers are not supported").into())
+        }
+    }
+}
+
+/// The dma-buf exported with [`Display::export_dma_buf`].
+#[derive(Debug)]
+pub struct ExportedDmaBuf {
+    /// The DRM fourcc format of the buffer.
+    pub fourcc: u32,
+
+    /// The DRM format modifier of the buffer.
+    pub modifier: u64,
+
+    /// The planes of the buffer.
+    pub planes: Vec<ExportedDmaBufPlane>,
+}
+
+/// The single plane of the exported dma-buf.
+#[derive(Debug)]
+pub struct ExportedDmaBufPlane {
+    /// The dma-buf file descriptor backing the plane.
+    pub fd: OwnedFd,
+
+    /// The offset of the plane from the start of the dma-buf in bytes.
+    pub offset: u32,
+
+    /// The stride of the plane in bytes.
+    pub stride: u32,
+}
+
+/// The DRM format modifier supported for the dma-buf import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DmaBufModifier {
+    /// The DRM format modifier.
+    pub modifier: u64,
+
+    /// Whether the images with this modifier could only be bound to the
+    /// `GL_TEXTURE_EXTERNAL_OES` target.
+    pub external_only: bool,
+}
+
+/// The description of the dma-buf to import with [`Display::import_dma_buf`].
+#[derive(Debug, Clone, Copy)]
+pub struct DmaBufImage<'a> {
+    /// The DRM fourcc format of the buffer.
+    pub fourcc: u32,
+
+    /// The width of the buffer in pixels.
+    pub width: NonZeroU32,
+
+    /// The height of the buffer in pixels.
+    pub height: NonZeroU32,
+
+    /// The planes of the buffer, at most [`MAX_DMA_BUF_PLANES`].
+    pub planes: &'a [DmaBufPlane<'a>],
+
+    /// The color space hint for the YUV formats.
+    pub color_space: Option<YuvColorSpace>,
+
+    /// The sample range hint for the YUV formats.
+    pub sample_range: Option<YuvSampleRange>,
+}
+
+/// The single plane of the dma-buf.
+#[derive(Debug, Clone, Copy)]
+pub struct DmaBufPlane<'a> {
+    /// The dma-buf file descriptor backing the plane.
+    pub fd: BorrowedFd<'a>,
+
+    /// The offset of the plane from the start of the dma-buf in bytes.
+    pub offset: u32,
+
+    /// The stride of the plane in bytes.
+    pub stride: u32,
+
+    /// The DRM format modifier of the plane.
+    ///
+    /// When [`None`] the driver will use the implicit modifier of the buffer.
+    pub modifier: Option<u64>,
+}
+
+/// The color space of the YUV formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YuvColorSpace {
+    /// ITU-R BT.601.
+    Rec601,
+    /// ITU-R BT.709.
+    Rec709,
+    /// ITU-R BT.2020.
+    Rec2020,
+}
+
+impl YuvColorSpace {
+    fn raw(self) -> EGLenum {
+        match self {
+            Self::Rec601 => egl::ITU_REC601_EXT,
+            Self::Rec709 => egl::ITU_REC709_EXT,
+            Self::Rec2020 => egl::ITU_REC2020_EXT,
+        }
+    }
+}
+
+/// The sample range of the YUV formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YuvSampleRange {
+    /// The full range of values.
+    Full,
+    /// The narrow, also known as limited, range of values.
+    Narrow,
+}
+
+impl YuvSampleRange {
+    fn raw(self) -> EGLenum {
+        match self {
+            Self::Full => egl::YUV_FULL_RANGE_EXT,
+            Self::Narrow => egl::YUV_NARROW_RANGE_EXT,
+        }
+    }
+}
@@ -0,0 +1,341 @@
+//! Everything related to the GBM platform.
+
+use std::ffi::{self, c_int};
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::num::NonZeroU32;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::path::Path;
+use std::ptr::NonNull;
+use std::rc::Rc;
+
+use libloading::Library;
+use once_cell::sync::Lazy;
+use raw_window_handle::{GbmWindowHandle, RawWindowHandle};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::prelude::*;
+use crate::surface::{SurfaceAttributesBuilder, WindowSurface};
+
+use super::config::Config;
+use super::context::PossiblyCurrentContext;
+use super::display::Display;
+use super::surface::Surface;
+
+/// The buffer will be presented on the screen.
+const GBM_BO_USE_SCANOUT: u32 = 1 << 0;
+
+/// The buffer will be used for rendering.
+const GBM_BO_USE_RENDERING: u32 = 1 << 2;
+
+/// The lazily loaded `libgbm`.
+static GBM: Lazy<Option<Gbm>> = Lazy::new(|| unsafe { Gbm::load() });
+
+/// The `libgbm` entry points used by glutin.
+pub(crate) struct Gbm {
+    create_device: unsafe extern "C" fn(c_int) -> *mut ffi::c_void,
+    device_destroy: unsafe extern "C" fn(*mut ffi::c_void),
+    surface_create: unsafe extern "C" fn(*mut ffi::c_void, u32, u32, u32, u32) -> *mut ffi::c_void,
+    surface_destroy: unsafe extern "C" fn(*mut ffi::c_void),
+    surface_lock_front_buffer: unsafe extern "C" fn(*mut ffi::c_void) -> *mut ffi::c_void,
+    surface_release_buffer: unsafe extern "C" fn(*mut ffi::c_void, *mut ffi::c_void),
+    surface_has_free_buffers: unsafe extern "C" fn(*mut ffi::c_void) -> c_int,
+    bo_get_width: unsafe extern "C" fn(*mut ffi::c_void) -> u32,
+    bo_get_height: unsafe extern "C" fn(*mut ffi::c_void) -> u32,
+    bo_get_stride: unsafe extern "C" fn(*mut ffi::c_void) -> u32,
+    bo_get_format: unsafe extern "C" fn(*mut ffi::c_void) -> u32,
+    bo_get_modifier: unsafe extern "C" fn(*mut ffi::c_void) -> u64,
+    bo_get_handle: unsafe extern "C" fn(*mut ffi::c_void) -> GbmBoHandle,
+    bo_get_fd: unsafe extern "C" fn(*mut ffi::c_void) -> c_int,
+    _lib: Library,
+}
+
+/// The `union gbm_bo_handle`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+union GbmBoHandle {
+    ptr: *mut ffi::c_void,
+    u32: u32,
+    u64: u64,
+}
+
+impl Gbm {
+    /// # Safety
+    ///
+    /// The loaded library must be `libgbm`.
+    unsafe fn load() -> Option<Self> {
+        let paths = ["libgbm.so.1", "libgbm.so"];
+        let lib = paths.iter().find_map(|path| unsafe { Library::new(path).ok() })?;
+
+        unsafe {
+            Some(Self {
+                create_device: *lib.get(b"gbm_create_device\0").ok()?,
+                device_destroy: *lib.get(b"gbm_device_destroy\0").ok()?,
+                surface_create: *lib.get(b"gbm_surface_create\0").ok()?,
+                surface_destroy: *lib.get(b"gbm_surface_destroy\0").ok()?,
+                surface_lock_front_buffer: *lib.get(b"gbm_surface_lock_front_buffer\0").ok()?,
+                surface_release_buffer: *lib.get(b"gbm_surface_release_buffer\0").ok()?,
+                surface_has_free_buffers: *lib.get(b"gbm_surface_has_free_buffers\0").ok()?,
+                bo_get_width: *lib.get(b"gbm_bo_get_width\0").ok()?,
+                bo_get_height: *lib.get(b"gbm_bo_get_height\0").ok()?,
+                bo_get_stride: *lib.get(b"gbm_bo_get_stride\0").ok()?,
+                bo_get_format: *lib.get(b"gbm_bo_get_format\0").ok()?,
+                bo_get_modifier: *lib.get(b"gbm_bo_get_modifier\0").ok()?,
+                bo_get_handle: *lib.get(b"gbm_bo_get_handle\0").ok()?,
+                bo_get_fd: *lib.get(b"gbm_bo_get_fd\0").ok()?,
+                _lib: lib,
+            })
+        }
+    }
+}
+
+/// The `gbm_device` owning the DRM node it was created from.
+pub(crate) struct GbmDevice {
+    gbm: &'static Gbm,
+    raw: NonNull<ffi::c_void>,
+    _file: File,
+}
+
+// The `gbm_device` is only used to create the display, which is thread safe.
+unsafe impl Send for GbmDevice {}
+unsafe impl Sync for GbmDevice {}
+
+impl GbmDevice {
+    /// Open the DRM node at `path` and create the `gbm_device` for it.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let gbm = match GBM.as_ref() {
+            Some(gbm) => gbm,
+            None => return Err(ErrorKind::NotFound.into()),
+        };
+
+        let file = OpenOptions::new().read(true).write(true).open(path).map_err(|err| {
+            Error::new(
+                err.raw_os_error().map(i64::from),
+                Some(err.to_string()),
+                ErrorKind::BadAccess,
+            )
+        })?;
+
+        let raw = unsafe { (gbm.create_device)(file.as_raw_fd()) };
+        match NonNull::new(raw) {
+            Some(raw) => Ok(Self { gbm, raw, _file: file }),
+            None => Err(ErrorKind::InitializationFailed.into()),
+        }
+    }
+
+    /// Get the raw `gbm_device`.
+    pub(crate) fn raw(&self) -> NonNull<ffi::c_void> {
+        self.raw
+    }
+}
+
+impl Drop for GbmDevice {
+    fn drop(&mut self) {
+        unsafe {
+            (self.gbm.device_destroy)(self.raw.as_ptr());
+        }
+    }
+}
+
+impl Display {
+    /// Create the [`GbmWindowSurface`] of the given size, which buffers could
+    /// be presented with KMS.
+    ///
+    /// The `gbm_surface` is allocated for scanout and rendering with the
+    /// [`Config::native_visual()`] of the `config`, which is the DRM fourcc
+    /// format on GBM displays.
+    ///
+    /// This function returns [`Err`] if the display wasn't created with
+    /// [`Display::from_drm_node()`].
+    pub fn create_gbm_window_surface(
+        &self,
+        config: &Config,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<GbmWindowSurface> {
+        let device = self
+            .inner
+            .gbm_device
+            .as_ref()
+            .ok_or(ErrorKind::NotSupported("the display doesn't own the GBM device"))?;
+
+        let raw = unsafe {
+            (device.gbm.surface_create)(
+                device.raw.as_ptr(),
+                width.get(),
+                height.get(),
+                config.native_visual(),
+                GBM_BO_USE_SCANOUT | GBM_BO_USE_RENDERING,
+            )
+        };
+        let raw = NonNull::new(raw).ok_or(ErrorKind::BadNativeWindow)?;
+        let gbm_surface = Rc::new(GbmSurface { gbm: device.gbm, raw, _display: self.clone() });
+
+        let raw_window_handle = RawWindowHandle::Gbm(GbmWindowHandle::new(raw));
+        let attrs = SurfaceAttributesBuilder::<WindowSurface>::new().build(
+            raw_window_handle,
+            width,
+            height,
+        );
+        let surface = unsafe { self.create_window_surface(config, &attrs)? };
+
+        Ok(GbmWindowSurface { surface, gbm_surface })
+    }
+}
+
+/// The `gbm_surface` destroyed once the surface and all its locked buffers
+/// are dropped.
+struct GbmSurface {
+    gbm: &'static Gbm,
+    raw: NonNull<ffi::c_void>,
+    // Keep the GBM device alive.
+    _display: Display,
+}
+
+impl Drop for GbmSurface {
+    fn drop(&mut self) {
+        unsafe {
+            (self.gbm.surface_destroy)(self.raw.as_ptr());
+        }
+    }
+}
+
+/// The EGL window surface backed by the `gbm_surface`.
+///
+/// The surface is rendered with [`Self::surface()`], while
+/// [`Self::swap_buffers()`] returns the rendered [`GbmBuffer`] to present
+/// with KMS.
+pub struct GbmWindowSurface {
+    // The EGL surface must be destroyed before the `gbm_surface`.
+    surface: Surface<WindowSurface>,
+    gbm_surface: Rc<GbmSurface>,
+}
+
+impl GbmWindowSurface {
+    /// Get the EGL surface to make current with the context.
+    pub fn surface(&self) -> &Surface<WindowSurface> {
+        &self.surface
+    }
+
+    /// Swap the buffers of the surface and lock the front buffer, which was
+    /// just rendered.
+    ///
+    /// The buffer is released back to the surface once the returned
+    /// [`GbmBuffer`] is dropped, thus it should be kept alive while it's
+    /// being scanned out. See [`Self::has_free_buffers()`].
+    pub fn swap_buffers(&self, context: &PossiblyCurrentContext) -> Result<GbmBuffer> {
+        self.surface.swap_buffers(context)?;
+
+        let gbm_surface = &self.gbm_surface;
+        let raw = unsafe { (gbm_surface.gbm.surface_lock_front_buffer)(gbm_surface.raw.as_ptr()) };
+        match NonNull::new(raw) {
+            Some(raw) => Ok(GbmBuffer { gbm_surface: gbm_surface.clone(), raw }),
+            None => Err(ErrorKind::BadSurface.into()),
+        }
+    }
+
+    /// Whether the surface has free buffers to render into.
+    ///
+    /// The rendering must not start when there're no free buffers, until any
+    /// of the locked [`GbmBuffer`] is dropped.
+    pub fn has_free_buffers(&self) -> bool {
+        unsafe {
+            (self.gbm_surface.gbm.surface_has_free_buffers)(self.gbm_surface.raw.as_ptr()) != 0
+        }
+    }
+
+    /// Get a raw handle to the `gbm_surface`.
+    pub fn raw_gbm_surface(&self) -> *mut ffi::c_void {
+        self.gbm_surface.raw.as_ptr()
+    }
+}
+
+impl fmt::Debug for GbmWindowSurface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GbmWindowSurface")
+            .field("surface", &self.surface)
+            .field("gbm_surface", &self.gbm_surface.raw)
+            .finish()
+    }
+}
+
+/// The `gbm_bo` locked from the [`GbmWindowSurface`].
+///
+/// The buffer is released back to the surface when dropped.
+pub struct GbmBuffer {
+    gbm_surface: Rc<GbmSurface>,
+    raw: NonNull<ffi::c_void>,
+}
+
+impl GbmBuffer {
+    /// The width of the buffer in pixels.
+    pub fn width(&self) -> u32 {
+        unsafe { (self.gbm_surface.gbm.bo_get_width)(self.raw.as_ptr()) }
+    }
+
+    /// The height of the buffer in pixels.
+    pub fn height(&self) -> u32 {
+        unsafe { (self.gbm_surface.gbm.bo_get_height)(self.raw.as_ptr()) }
+    }
+
+    /// The stride of the buffer in bytes.
+    pub fn stride(&self) -> u32 {
+        unsafe { (self.gbm_surface.gbm.bo_get_stride)(self.raw.as_ptr()) }
+    }
+
+    /// The DRM fourcc format of the buffer.
+    pub fn format(&self) -> u32 {
+        unsafe { (self.gbm_surface.gbm.bo_get_format)(self.raw.as_ptr()) }
+    }
+
+    /// The DRM format modifier of the buffer.
+    pub fn modifier(&self) -> u64 {
+        unsafe { (self.gbm_surface.gbm.bo_get_modifier)(self.raw.as_ptr()) }
+    }
+
+    /// The GEM handle of the buffer on the DRM node of the display, which
+    /// could be used to create the framebuffer.
+    pub fn handle(&self) -> u32 {
+        unsafe { (self.gbm_surface.gbm.bo_get_handle)(self.raw.as_ptr()).u32 }
+    }
+
+    /// Export the buffer as the dma-buf.
+    pub fn dma_buf_fd(&self) -> Result<OwnedFd> {
+        let fd = unsafe { (self.gbm_surface.gbm.bo_get_fd)(self.raw.as_ptr()) };
+        if fd < 0 {
+            return Err(ErrorKind::BadAccess.into());
+        }
+
+        Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+
+    /// Get a raw handle to the `gbm_bo`.
+    pub fn raw_bo(&self) -> *mut ffi::c_void {
+        self.raw.as_ptr()
+    }
+}
+
+impl Drop for GbmBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            (self.gbm_surface.gbm.surface_release_buffer)(
+                self.gbm_surface.raw.as_ptr(),
+                self.raw.as_ptr(),
+            );
+        }
+    }
+}
+
+impl fmt::Debug for GbmBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GbmBuffer")
+            .field("gbm_surface", &self.gbm_surface.raw)
+            .field("raw", &self.raw)
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("format", &self.format())
+            .field("modifier", &self.modifier())
+            .finish()
+    }
+}
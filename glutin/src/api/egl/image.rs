@@ -0,0 +1,226 @@
+//! Everything related to `EGLImage`.
+
+use std::fmt;
+use std::num::NonZeroU32;
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{
+    EGLAttrib, EGLClientBuffer, EGLContext, EGLImage, EGLenum, EGLint,
+};
+
+use crate::context::Version;
+use crate::error::{ErrorKind, Result};
+
+use super::context::PossiblyCurrentContext;
+use super::display::Display;
+
+impl Display {
+    /// Create an [`Image`] from the client API resource of the `context`.
+    ///
+    /// The resource must be complete and not be used as a sibling of any other
+    /// image. Once created, the image could be bound to the textures and
+    /// renderbuffers of any context of the display, for example, with
+    /// `glEGLImageTargetTexture2DOES`.
+    ///
+    /// This function uses the EGL 1.5 entry points when they are available and
+    /// falls back to the `EGL_KHR_image_base` extension otherwise. In the
+    /// latter case the `EGL_KHR_gl_*_image` extension for the particular
+    /// `source` is required.
+    pub fn create_image(
+        &self,
+        context: &PossiblyCurrentContext,
+        source: ImageSource,
+    ) -> Result<Image> {
+        let (target, extension, buffer, level, zoffset) = match source {
+            ImageSource::Texture2D { texture, level } => {
+                (egl::GL_TEXTURE_2D, "EGL_KHR_gl_texture_2D_image", texture, level, None)
+            },
+            ImageSource::TextureCubeMap { texture, face, level } => {
+                (face.target(), "EGL_KHR_gl_texture_cubemap_image", texture, level, None)
+            },
+            ImageSource::Texture3D { texture, level, zoffset } => {
+                (egl::GL_TEXTURE_3D, "EGL_KHR_gl_texture_3D_image", texture, level, Some(zoffset))
+            },
+            ImageSource::Renderbuffer(renderbuffer) => {
+                (egl::GL_RENDERBUFFER, "EGL_KHR_gl_renderbuffer_image", renderbuffer, 0, None)
+            },
+        };
+
+        if !self.supports_core_image()
+            && (!self.inner.display_extensions.contains("EGL_KHR_image_base")
+                || !self.inner.display_extensions.contains(extension))
+        {
+            return Err(ErrorKind::NotSupported("the image source is not supported").into());
+        }
+
+        let mut attrs = Vec::<EGLAttrib>::with_capacity(4);
+        if level != 0 {
+            attrs.push(egl::GL_TEXTURE_LEVEL as EGLAttrib);
+            attrs.push(level as EGLAttrib);
+        }
+
+        if let Some(zoffset) = zoffset {
+            attrs.push(egl::GL_TEXTURE_ZOFFSET as EGLAttrib);
+            attrs.push(zoffset as EGLAttrib);
+        }
+
+        unsafe {
+            self.create_image_raw(
+                context.inner.raw(),
+                target,
+                buffer.get() as usize as EGLClientBuffer,
+                &attrs,
+            )
+        }
+    }
+
+    /// Create the image for the `target` with `attrs` not terminated by
+    /// `EGL_NONE`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the `target` is supported by the display
+    /// and that `context`, `buffer` and `attrs` are valid for it.
+    pub(crate) unsafe fn create_image_raw(
+        &self,
+        context: EGLContext,
+        target: EGLenum,
+        buffer: EGLClientBuffer,
+        attrs: &[EGLAttrib],
+    ) -> Result<Image> {
+        let raw = unsafe {
+            if self.supports_core_image() {
+                let mut attrs = attrs.to_vec();
+                attrs.push(egl::NONE as EGLAttrib);
+                self.inner.egl.CreateImage(*self.inner.raw, context, target, buffer, attrs.as_ptr())
+            } else {
+                let mut attrs: Vec<EGLint> = attrs.iter().map(|&attr| attr as EGLint).collect();
+                attrs.push(egl::NONE as EGLint);
+                self.inner.egl.CreateImageKHR(
+                    *self.inner.raw,
+                    context,
+                    target,
+                    buffer,
+                    attrs.as_ptr(),
+                )
+            }
+        };
+
+        if raw == egl::NO_IMAGE {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(Image { display: self.clone(), raw })
+    }
+
+    /// Whether the EGL 1.5 image entry points could be used on the display.
+    fn supports_core_image(&self) -> bool {
+        self.inner.version >= Version::new(1, 5)
+    }
+}
+
+/// The client API resource used to create an [`Image`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSource {
+    /// The mipmap `level` of the 2D `texture`.
+    Texture2D {
+        /// The name of the texture.
+        texture: NonZeroU32,
+        /// The mipmap level of the texture.
+        level: u32,
+    },
+
+    /// The `face` of the mipmap `level` of the cube map `texture`.
+    TextureCubeMap {
+        /// The name of the texture.
+        texture: NonZeroU32,
+        /// The face of the cube map.
+        face: CubeMapFace,
+        /// The mipmap level of the texture.
+        level: u32,
+    },
+
+    /// The `zoffset` slice of the mipmap `level` of the 3D `texture`.
+    Texture3D {
+        /// The name of the texture.
+        texture: NonZeroU32,
+        /// The mipmap level of the texture.
+        level: u32,
+        /// The depth offset of the slice.
+        zoffset: u32,
+    },
+
+    /// The renderbuffer with the given name.
+    Renderbuffer(NonZeroU32),
+}
+
+/// The face of the cube map texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CubeMapFace {
+    /// The `GL_TEXTURE_CUBE_MAP_POSITIVE_X` face.
+    PositiveX,
+    /// The `GL_TEXTURE_CUBE_MAP_NEGATIVE_X` face.
+    NegativeX,
+    /// The `GL_TEXTURE_CUBE_MAP_POSITIVE_Y` face.
+    PositiveY,
+    /// The `GL_TEXTURE_CUBE_MAP_NEGATIVE_Y` face.
+    NegativeY,
+    /// The `GL_TEXTURE_CUBE_MAP_POSITIVE_Z` face.
+    PositiveZ,
+    /// The `GL_TEXTURE_CUBE_MAP_NEGATIVE_Z` face.
+    NegativeZ,
+}
+
+impl CubeMapFace {
+    fn target(self) -> EGLenum {
+        match self {
+            Self::PositiveX => egl::GL_TEXTURE_CUBE_MAP_POSITIVE_X,
+            Self::NegativeX => egl::GL_TEXTURE_CUBE_MAP_NEGATIVE_X,
+            Self::PositiveY => egl::GL_TEXTURE_CUBE_MAP_POSITIVE_Y,
+            Self::NegativeY => egl::GL_TEXTURE_CUBE_MAP_NEGATIVE_Y,
+            Self::PositiveZ => egl::GL_TEXTURE_CUBE_MAP_POSITIVE_Z,
+            Self::NegativeZ => egl::GL_TEXTURE_CUBE_MAP_NEGATIVE_Z,
+        }
+    }
+}
+
+/// A wrapper around `EGLImage`.
+///
+/// The image keeps the display alive and is destroyed when dropped. The
+/// client API resources bound to the image stay valid after that.
+pub struct Image {
+    display: Display,
+    raw: EGLImage,
+}
+
+// The image is a display level object, thus could be used from any thread.
+unsafe impl Send for Image {}
+unsafe impl Sync for Image {}
+
+impl Image {
+    /// Get a raw handle to the `EGLImage`.
+    pub fn raw_image(&self) -> EGLImage {
+        self.raw
+    }
+}
+
+impl Drop for Image {
+    fn drop(&mut self) {
+        unsafe {
+            if self.display.supports_core_image() {
+                self.display.inner.egl.DestroyImage(*self.display.inner.raw, self.raw);
+            } else {
+                self.display.inner.egl.DestroyImageKHR(*self.display.inner.raw, self.raw);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Image")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
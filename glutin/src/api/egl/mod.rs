@@ -22,11 +22,22 @@ use libloading::os::windows as libloading_os;
 use crate::error::{Error, ErrorKind, Result};
 use crate::lib_loading::{SymLoading, SymWrapper};
 
+pub mod blob_cache;
 pub mod config;
 pub mod context;
 pub mod device;
 pub mod display;
+#[cfg(unix)]
+pub mod dma_buf;
+#[cfg(gbm_platform)]
+pub mod gbm;
+pub mod image;
+pub mod output;
+pub mod stream;
 pub mod surface;
+pub mod sync;
+#[cfg(wayland_platform)]
+pub mod wayland;
 
 // WARNING: If this implementation is ever changed to unload or replace the
 // library, note that public API functions currently retirm `&'static str`ings
@@ -77,6 +88,9 @@ impl SymLoading for egl::Egl {
         egl::UnbindWaylandDisplayWL::load_with(loader);
         egl::QueryWaylandBufferWL::load_with(loader);
         egl::CreateWaylandBufferFromImageWL::load_with(loader);
+        egl::QueryDeviceBinaryEXT::load_with(loader);
+        egl::GetDisplayDriverName::load_with(loader);
+        egl::GetDisplayDriverConfig::load_with(loader);
 
         Self::load_with(loader)
     }
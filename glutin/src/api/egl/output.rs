@@ -0,0 +1,285 @@
+//! Everything related to the EGL outputs presenting directly to the display.
+
+use std::num::NonZeroU32;
+use std::{fmt, ptr};
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLOutputLayerEXT, EGLOutputPortEXT, EGLenum, EGLint};
+
+use crate::error::{ErrorKind, Result};
+use crate::surface::WindowSurface;
+
+use super::config::Config;
+use super::display::Display;
+use super::surface::Surface;
+
+impl Display {
+    /// Get the output layers of the display matching the `filter`.
+    ///
+    /// The layers are the planes of the display which the [`Stream`] could
+    /// be presented on with [`Stream::connect_output_layer_consumer()`].
+    ///
+    /// This function returns [`Err`] if the `EGL_EXT_output_base` extension is
+    /// not available or the `filter` is not supported for the layers.
+    ///
+    /// [`Stream`]: super::stream::Stream
+    /// [`Stream::connect_output_layer_consumer()`]: super::stream::Stream::connect_output_layer_consumer
+    pub fn output_layers(&self, filter: OutputFilter) -> Result<Vec<OutputLayer>> {
+        self.check_output_base()?;
+
+        let attrs = filter.attributes();
+        let display = &self.inner;
+        let layers = query_outputs(|outputs, max_outputs, num_outputs| unsafe {
+            display.egl.GetOutputLayersEXT(
+                *display.raw,
+                attrs.as_ptr(),
+                outputs,
+                max_outputs,
+                num_outputs,
+            )
+        })?;
+
+        Ok(layers.into_iter().map(|raw| OutputLayer { display: self.clone(), raw }).collect())
+    }
+
+    /// Get the output ports of the display matching the `filter`.
+    ///
+    /// The ports are the connections of the display to the physical screens.
+    ///
+    /// This function returns [`Err`] if the `EGL_EXT_output_base` extension is
+    /// not available or the `filter` is not supported for the ports.
+    pub fn output_ports(&self, filter: OutputFilter) -> Result<Vec<OutputPort>> {
+        self.check_output_base()?;
+
+        let attrs = filter.attributes();
+        let display = &self.inner;
+        let ports = query_outputs(|outputs, max_outputs, num_outputs| unsafe {
+            display.egl.GetOutputPortsEXT(
+                *display.raw,
+                attrs.as_ptr(),
+                outputs,
+                max_outputs,
+                num_outputs,
+            )
+        })?;
+
+        Ok(ports.into_iter().map(|raw| OutputPort { display: self.clone(), raw }).collect())
+    }
+
+    /// Create the window surface presenting on the DRM `plane` through the
+    /// stream connected to its output layer.
+    ///
+    /// The `config` must support the stream surfaces, see
+    /// [`Config::supports_stream_surfaces()`].
+    pub(crate) fn create_drm_plane_surface(
+        &self,
+        config: &Config,
+        plane: u32,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Surface<WindowSurface>> {
+        if !config.supports_stream_surfaces() {
+            return Err(ErrorKind::NotSupported(
+                "the DRM plane surfaces require the config supporting the stream surfaces",
+            )
+            .into());
+        }
+
+        let layer = self
+            .output_layers(OutputFilter::DrmPlane(plane))?
+            .into_iter()
+            .next()
+            .ok_or(ErrorKind::BadNativeWindow)?;
+
+        let stream = self.create_stream()?;
+        stream.connect_output_layer_consumer(&layer)?;
+
+        let mut surface = self.create_stream_producer_surface(config, &stream, width, height)?;
+        surface.stream = Some(stream);
+
+        Ok(surface)
+    }
+
+    fn check_output_base(&self) -> Result<()> {
+        if self.inner.display_extensions.contains("EGL_EXT_output_base") {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("EGL outputs are not supported").into())
+        }
+    }
+}
+
+/// Query the outputs with the `get` function following the
+/// `eglGetOutput*EXT` semantics.
+fn query_outputs<T>(
+    get: impl Fn(*mut T, EGLint, *mut EGLint) -> egl::types::EGLBoolean,
+) -> Result<Vec<T>> {
+    let mut num_outputs = 0;
+    if get(ptr::null_mut(), 0, &mut num_outputs) == egl::FALSE {
+        return Err(super::check_error().err().unwrap());
+    }
+
+    let mut outputs = Vec::with_capacity(num_outputs as usize);
+    if get(outputs.as_mut_ptr(), num_outputs, &mut num_outputs) == egl::FALSE {
+        return Err(super::check_error().err().unwrap());
+    }
+
+    unsafe {
+        outputs.set_len(num_outputs as usize);
+    }
+
+    Ok(outputs)
+}
+
+/// The filter to match the outputs against.
+///
+/// The DRM filters require the `EGL_EXT_output_drm` extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputFilter {
+    /// Match all the outputs.
+    All,
+    /// Match the layers and ports associated with the DRM CRTC id.
+    DrmCrtc(u32),
+    /// Match the layer of the DRM plane id.
+    DrmPlane(u32),
+    /// Match the port of the DRM connector id.
+    DrmConnector(u32),
+}
+
+impl OutputFilter {
+    fn attributes(self) -> Vec<EGLAttrib> {
+        let (attr, id) = match self {
+            Self::All => return vec![egl::NONE as EGLAttrib],
+            Self::DrmCrtc(id) => (egl::DRM_CRTC_EXT, id),
+            Self::DrmPlane(id) => (egl::DRM_PLANE_EXT, id),
+            Self::DrmConnector(id) => (egl::DRM_CONNECTOR_EXT, id),
+        };
+
+        vec![attr as EGLAttrib, id as EGLAttrib, egl::NONE as EGLAttrib]
+    }
+}
+
+/// A wrapper around `EGLOutputLayerEXT`.
+///
+/// The layer is owned by the display and stays valid as long as it's alive.
+#[derive(Clone)]
+pub struct OutputLayer {
+    display: Display,
+    raw: EGLOutputLayerEXT,
+}
+
+// The layer is a display level object, thus could be used from any thread.
+unsafe impl Send for OutputLayer {}
+unsafe impl Sync for OutputLayer {}
+
+impl OutputLayer {
+    /// The DRM plane id of the layer.
+    pub fn drm_plane(&self) -> Option<u32> {
+        self.query(egl::DRM_PLANE_EXT)
+    }
+
+    /// The DRM CRTC id associated with the layer.
+    pub fn drm_crtc(&self) -> Option<u32> {
+        self.query(egl::DRM_CRTC_EXT)
+    }
+
+    /// Set the minimum number of the display refresh cycles between the
+    /// frames presented on the layer.
+    pub fn set_swap_interval(&self, interval: u32) -> Result<()> {
+        let display = &self.display.inner;
+        unsafe {
+            if display.egl.OutputLayerAttribEXT(
+                *display.raw,
+                self.raw,
+                egl::SWAP_INTERVAL_EXT as EGLint,
+                interval as EGLAttrib,
+            ) == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Get a raw handle to the `EGLOutputLayerEXT`.
+    pub fn raw_layer(&self) -> EGLOutputLayerEXT {
+        self.raw
+    }
+
+    fn query(&self, attribute: EGLenum) -> Option<u32> {
+        let display = &self.display.inner;
+        let mut value = 0;
+        unsafe {
+            let res = display.egl.QueryOutputLayerAttribEXT(
+                *display.raw,
+                self.raw,
+                attribute as EGLint,
+                &mut value,
+            );
+            (res != egl::FALSE).then_some(value as u32)
+        }
+    }
+}
+
+impl fmt::Debug for OutputLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OutputLayer")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
+
+/// A wrapper around `EGLOutputPortEXT`.
+///
+/// The port is owned by the display and stays valid as long as it's alive.
+#[derive(Clone)]
+pub struct OutputPort {
+    display: Display,
+    raw: EGLOutputPortEXT,
+}
+
+// The port is a display level object, thus could be used from any thread.
+unsafe impl Send for OutputPort {}
+unsafe impl Sync for OutputPort {}
+
+impl OutputPort {
+    /// The DRM connector id of the port.
+    pub fn drm_connector(&self) -> Option<u32> {
+        self.query(egl::DRM_CONNECTOR_EXT)
+    }
+
+    /// The DRM CRTC id associated with the port.
+    pub fn drm_crtc(&self) -> Option<u32> {
+        self.query(egl::DRM_CRTC_EXT)
+    }
+
+    /// Get a raw handle to the `EGLOutputPortEXT`.
+    pub fn raw_port(&self) -> EGLOutputPortEXT {
+        self.raw
+    }
+
+    fn query(&self, attribute: EGLenum) -> Option<u32> {
+        let display = &self.display.inner;
+        let mut value = 0;
+        unsafe {
+            let res = display.egl.QueryOutputPortAttribEXT(
+                *display.raw,
+                self.raw,
+                attribute as EGLint,
+                &mut value,
+            );
+            (res != egl::FALSE).then_some(value as u32)
+        }
+    }
+}
+
+impl fmt::Debug for OutputPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OutputPort")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
@@ -0,0 +1,358 @@
+//! Everything related to `EGLStreamKHR`.
+
+use std::fmt;
+use std::num::NonZeroU32;
+#[cfg(unix)]
+use std::os::fd::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
+use std::time::Duration;
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLStreamKHR, EGLenum, EGLint, EGLuint64KHR};
+
+use crate::error::{ErrorKind, Result};
+use crate::surface::WindowSurface;
+
+use super::config::Config;
+use super::context::PossiblyCurrentContext;
+use super::display::Display;
+use super::output::OutputLayer;
+use super::surface::Surface;
+
+impl Display {
+    /// Create a new [`Stream`] to transfer the frames from its producer to
+    /// its consumer.
+    ///
+    /// This function returns [`Err`] if the `EGL_KHR_stream` extension is not
+    /// available.
+    pub fn create_stream(&self) -> Result<Stream> {
+        self.check_stream_extension("EGL_KHR_stream")?;
+
+        let attrs = [egl::NONE as EGLint];
+        let raw = unsafe { self.inner.egl.CreateStreamKHR(*self.inner.raw, attrs.as_ptr()) };
+        self.wrap_stream(raw)
+    }
+
+    /// Create the [`Stream`] from the `fd` of the stream created in the other
+    /// process with [`Stream::file_descriptor()`].
+    ///
+    /// The `fd` is not used after this call returns and could be closed.
+    ///
+    /// This function returns [`Err`] if the `EGL_KHR_stream_cross_process_fd`
+    /// extension is not available.
+    #[cfg(unix)]
+    pub fn create_stream_from_fd(&self, fd: BorrowedFd<'_>) -> Result<Stream> {
+        self.check_stream_extension("EGL_KHR_stream_cross_process_fd")?;
+
+        let raw = unsafe {
+            self.inner.egl.CreateStreamFromFileDescriptorKHR(*self.inner.raw, fd.as_raw_fd())
+        };
+        self.wrap_stream(raw)
+    }
+
+    /// Create the producer surface of the `stream`, which frames are inserted
+    /// into the stream when swapping the buffers.
+    ///
+    /// The `config` must support the `EGL_STREAM_BIT_KHR` surface type, see
+    /// [`Config::supports_stream_surfaces()`].
+    ///
+    /// This function returns [`Err`] if the
+    /// `EGL_KHR_stream_producer_eglsurface` extension is not available or the
+    /// `config` doesn't support the stream surfaces.
+    pub fn create_stream_producer_surface(
+        &self,
+        config: &Config,
+        stream: &Stream,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Surface<WindowSurface>> {
+        self.check_stream_extension("EGL_KHR_stream_producer_eglsurface")?;
+
+        if !config.supports_stream_surfaces() {
+            return Err(ErrorKind::NotSupported(
+                "the config doesn't support the stream producer surfaces",
+            )
+            .into());
+        }
+
+        let attrs = [
+            egl::WIDTH as EGLint,
+            width.get() as EGLint,
+            egl::HEIGHT as EGLint,
+            height.get() as EGLint,
+            egl::NONE as EGLint,
+        ];
+
+        let raw = unsafe {
+            self.inner.egl.CreateStreamProducerSurfaceKHR(
+                *self.inner.raw,
+                *config.inner.raw,
+                stream.raw,
+                attrs.as_ptr(),
+            )
+        };
+
+        if raw == egl::NO_SURFACE {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(Surface::from_raw(self.clone(), config.clone(), raw))
+    }
+
+    fn wrap_stream(&self, raw: EGLStreamKHR) -> Result<Stream> {
+        if raw == egl::NO_STREAM_KHR {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(Stream { display: self.clone(), raw })
+    }
+
+    fn check_stream_extension(&self, extension: &str) -> Result<()> {
+        let extensions = &self.inner.display_extensions;
+        if extensions.contains("EGL_KHR_stream") && extensions.contains(extension) {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("the stream functionality is not supported").into())
+        }
+    }
+}
+
+/// A wrapper around `EGLStreamKHR`.
+///
+/// The stream is destroyed when dropped.
+pub struct Stream {
+    display: Display,
+    raw: EGLStreamKHR,
+}
+
+// The stream is a display level object, thus could be used from any thread.
+unsafe impl Send for Stream {}
+unsafe impl Sync for Stream {}
+
+impl Stream {
+    /// Connect the external texture currently bound to the
+    /// `GL_TEXTURE_EXTERNAL_OES` target of the `context` as the consumer of
+    /// the stream.
+    ///
+    /// The `context` must be current on the calling thread.
+    ///
+    /// This function returns [`Err`] if the
+    /// `EGL_KHR_stream_consumer_gltexture` extension is not available.
+    pub fn connect_gl_texture_consumer(&self, context: &PossiblyCurrentContext) -> Result<()> {
+        self.display.check_stream_extension("EGL_KHR_stream_consumer_gltexture")?;
+
+        context.inner.bind_api();
+
+        let display = &self.display.inner;
+        unsafe {
+            if display.egl.StreamConsumerGLTextureExternalKHR(*display.raw, self.raw) == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Connect the output `layer` as the consumer of the stream, so the
+    /// frames inserted by the producer are presented on it.
+    ///
+    /// This function returns [`Err`] if the
+    /// `EGL_EXT_stream_consumer_egloutput` extension is not available.
+    pub fn connect_output_layer_consumer(&self, layer: &OutputLayer) -> Result<()> {
+        self.display.check_stream_extension("EGL_EXT_stream_consumer_egloutput")?;
+
+        let display = &self.display.inner;
+        unsafe {
+            if display.egl.StreamConsumerOutputEXT(*display.raw, self.raw, layer.raw_layer())
+                == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Latch the most recent frame of the stream into the consumer texture.
+    ///
+    /// The call blocks up to the consumer acquire timeout, see
+    /// [`Self::set_consumer_acquire_timeout()`], when there's no new frame.
+    ///
+    /// The `context` the consumer was connected with must be current on the
+    /// calling thread.
+    pub fn acquire(&self, context: &PossiblyCurrentContext) -> Result<()> {
+        context.inner.bind_api();
+
+        let display = &self.display.inner;
+        unsafe {
+            if display.egl.StreamConsumerAcquireKHR(*display.raw, self.raw) == egl::FALSE {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Release the frame latched with [`Self::acquire()`] back to the stream.
+    ///
+    /// The `context` the consumer was connected with must be current on the
+    /// calling thread.
+    pub fn release(&self, context: &PossiblyCurrentContext) -> Result<()> {
+        context.inner.bind_api();
+
+        let display = &self.display.inner;
+        unsafe {
+            if display.egl.StreamConsumerReleaseKHR(*display.raw, self.raw) == egl::FALSE {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the current state of the stream.
+    pub fn state(&self) -> Result<StreamState> {
+        let state = self.query(egl::STREAM_STATE_KHR)?;
+        StreamState::from_raw(state as EGLenum).ok_or_else(|| ErrorKind::Misc.into())
+    }
+
+    /// The number of frames inserted into the stream by the producer.
+    pub fn producer_frame(&self) -> Result<u64> {
+        self.query_u64(egl::PRODUCER_FRAME_KHR)
+    }
+
+    /// The number of the frame most recently acquired by the consumer.
+    pub fn consumer_frame(&self) -> Result<u64> {
+        self.query_u64(egl::CONSUMER_FRAME_KHR)
+    }
+
+    /// Set the latency between the frame being inserted by the producer and
+    /// presented by the consumer, which the producer may use to schedule the
+    /// frames.
+    pub fn set_consumer_latency(&self, latency: Duration) -> Result<()> {
+        self.set_attribute(egl::CONSUMER_LATENCY_USEC_KHR, latency)
+    }
+
+    /// Set how long [`Self::acquire()`] could block waiting for the new frame.
+    pub fn set_consumer_acquire_timeout(&self, timeout: Duration) -> Result<()> {
+        self.set_attribute(egl::CONSUMER_ACQUIRE_TIMEOUT_USEC_KHR, timeout)
+    }
+
+    /// Get the file descriptor to create the same stream in the other process
+    /// with [`Display::create_stream_from_fd()`].
+    ///
+    /// The file descriptor could only be retrieved before the stream is
+    /// connected.
+    ///
+    /// This function returns [`Err`] if the `EGL_KHR_stream_cross_process_fd`
+    /// extension is not available.
+    #[cfg(unix)]
+    pub fn file_descriptor(&self) -> Result<OwnedFd> {
+        self.display.check_stream_extension("EGL_KHR_stream_cross_process_fd")?;
+
+        let display = &self.display.inner;
+        let fd = unsafe { display.egl.GetStreamFileDescriptorKHR(*display.raw, self.raw) };
+        if fd == egl::NO_FILE_DESCRIPTOR_KHR {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+
+    /// Get a raw handle to the `EGLStreamKHR`.
+    pub fn raw_stream(&self) -> EGLStreamKHR {
+        self.raw
+    }
+
+    fn set_attribute(&self, attribute: EGLenum, value: Duration) -> Result<()> {
+        let value = value.as_micros().min(EGLint::MAX as u128) as EGLint;
+
+        let display = &self.display.inner;
+        unsafe {
+            if display.egl.StreamAttribKHR(*display.raw, self.raw, attribute, value) == egl::FALSE {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn query(&self, attribute: EGLenum) -> Result<EGLint> {
+        let display = &self.display.inner;
+        let mut value = 0;
+        unsafe {
+            if display.egl.QueryStreamKHR(*display.raw, self.raw, attribute, &mut value)
+                == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn query_u64(&self, attribute: EGLenum) -> Result<u64> {
+        let display = &self.display.inner;
+        let mut value: EGLuint64KHR = 0;
+        unsafe {
+            if display.egl.QueryStreamu64KHR(*display.raw, self.raw, attribute, &mut value)
+                == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+impl Drop for Stream {
+    fn drop(&mut self) {
+        unsafe {
+            self.display.inner.egl.DestroyStreamKHR(*self.display.inner.raw, self.raw);
+        }
+    }
+}
+
+impl fmt::Debug for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stream")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
+
+/// The state of the [`Stream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamState {
+    /// The stream was created, but neither the consumer nor the producer are
+    /// connected yet.
+    Created,
+    /// The consumer is connected, but the producer is not.
+    Connecting,
+    /// Both consumer and producer are connected, but no frames were inserted
+    /// yet.
+    Empty,
+    /// There's the frame which wasn't acquired by the consumer yet.
+    NewFrameAvailable,
+    /// The most recent frame was already acquired by the consumer.
+    OldFrameAvailable,
+    /// The consumer or the producer were destroyed, thus the stream is no
+    /// longer usable.
+    Disconnected,
+}
+
+impl StreamState {
+    fn from_raw(raw: EGLenum) -> Option<Self> {
+        match raw {
+            egl::STREAM_STATE_CREATED_KHR => Some(Self::Created),
+            egl::STREAM_STATE_CONNECTING_KHR => Some(Self::Connecting),
+            egl::STREAM_STATE_EMPTY_KHR => Some(Self::Empty),
+            egl::STREAM_STATE_NEW_FRAME_AVAILABLE_KHR => Some(Self::NewFrameAvailable),
+            egl::STREAM_STATE_OLD_FRAME_AVAILABLE_KHR => Some(Self::OldFrameAvailable),
+            egl::STREAM_STATE_DISCONNECTED_KHR => Some(Self::Disconnected),
+            _ => None,
+        }
+    }
+}
@@ -24,6 +24,7 @@ use crate::surface::{
 use super::config::Config;
 use super::context::PossiblyCurrentContext;
 use super::display::Display;
+use super::stream::Stream;
 
 /// Hint for the attribute list size.
 const ATTR_SIZE_HINT: usize = 8;
@@ -62,6 +63,7 @@ impl Display {
         Ok(Surface {
             display: self.clone(),
             native_window: None,
+            stream: None,
             config,
             raw: surface,
             _ty: PhantomData,
@@ -152,6 +154,7 @@ impl Display {
             display: self.clone(),
             config,
             native_window: None,
+            stream: None,
             raw: surface,
             _ty: PhantomData,
         })
@@ -162,6 +165,16 @@ impl Display {
         config: &Config,
         surface_attributes: &SurfaceAttributes<WindowSurface>,
     ) -> Result<Surface<WindowSurface>> {
+        // Present on the DRM plane through the stream connected to its output layer.
+        if let Some(RawWindowHandle::Drm(window_handle)) = surface_attributes.raw_window_handle {
+            return self.create_drm_plane_surface(
+                config,
+                window_handle.plane,
+                surface_attributes.width.unwrap(),
+                surface_attributes.height.unwrap(),
+            );
+        }
+
         // Create native window.
         let native_window = NativeWindow::new(
             surface_attributes.width.unwrap(),
@@ -229,6 +242,7 @@ impl Display {
             display: self.clone(),
             config,
             native_window: Some(native_window),
+            stream: None,
             raw: surface,
             _ty: PhantomData,
         })
@@ -249,6 +263,9 @@ pub struct Surface<T: SurfaceTypeTrait> {
     config: Config,
     pub(crate) raw: EGLSurface,
     native_window: Option<NativeWindow>,
+    /// The stream presenting the frames of the surface, which is destroyed
+    /// after the surface.
+    pub(crate) stream: Option<Stream>,
     _ty: PhantomData<T>,
 }
 
@@ -256,51 +273,58 @@ pub struct Surface<T: SurfaceTypeTrait> {
 unsafe impl<T: SurfaceTypeTrait> Send for Surface<T> {}
 
 impl<T: SurfaceTypeTrait> Surface<T> {
+    /// Wrap the `EGLSurface` without the native window.
+    pub(crate) fn from_raw(display: Display, config: Config, raw: EGLSurface) -> Self {
+        Self { display, config, native_window: None, stream: None, raw, _ty: PhantomData }
+    }
+
     /// Swaps the underlying back buffers when the surface is not single
     /// buffered and pass the [`Rect`] information to the system
-    /// compositor. Providing empty slice will damage the entire surface.
-    ///
-    /// When the underlying extensions are not supported the function acts like
-    /// [`Self::swap_buffers`].
+    /// compositor.
     ///
-    /// This Api doesn't do any partial rendering, it just provides hints for
-    /// the system compositor.
+    /// This is the same as [`GlSurface::swap_buffers_with_damage()`].
     pub fn swap_buffers_with_damage(
         &self,
         context: &PossiblyCurrentContext,
         rects: &[Rect],
     ) -> Result<()> {
-        context.inner.bind_api();
+        GlSurface::swap_buffers_with_damage(self, context, rects)
+    }
 
-        let res = unsafe {
-            if self.display.inner.display_extensions.contains("EGL_KHR_swap_buffers_with_damage") {
-                self.display.inner.egl.SwapBuffersWithDamageKHR(
-                    *self.display.inner.raw,
-                    self.raw,
-                    rects.as_ptr() as *mut _,
-                    rects.len() as _,
-                )
-            } else if self
-                .display
-                .inner
-                .display_extensions
-                .contains("EGL_EXT_swap_buffers_with_damage")
+    /// Declare the `rects` of the back buffer which are going to be redrawn
+    /// in the current frame, so the contents outside of them could be
+    /// preserved by the driver without copying the whole buffer.
+    ///
+    /// The extension requires the damage region to be set only once per frame,
+    /// after the [`buffer age`] was queried and before any rendering commands
+    /// are issued to the surface, which must be current. The age is queried by
+    /// this function to satisfy that requirement, while the rest is up to the
+    /// caller. Providing empty slice will damage the entire surface.
+    ///
+    /// This function returns [`Err`] if the `EGL_KHR_partial_update` extension
+    /// is not available, see [`DisplayFeatures::PARTIAL_UPDATE`].
+    ///
+    /// [`buffer age`]: crate::surface::GlSurface::buffer_age
+    /// [`DisplayFeatures::PARTIAL_UPDATE`]: crate::display::DisplayFeatures::PARTIAL_UPDATE
+    pub fn set_damage_region(&self, rects: &[Rect]) -> Result<()> {
+        if !self.display.inner.display_extensions.contains("EGL_KHR_partial_update") {
+            return Err(ErrorKind::NotSupported("partial update is not supported").into());
+        }
+
+        unsafe {
+            self.raw_attribute(egl::BUFFER_AGE_KHR as EGLint);
+
+            if self.display.inner.egl.SetDamageRegionKHR(
+                *self.display.inner.raw,
+                self.raw,
+                rects.as_ptr() as *mut _,
+                rects.len() as _,
+            ) == egl::FALSE
             {
-                self.display.inner.egl.SwapBuffersWithDamageEXT(
-                    *self.display.inner.raw,
-                    self.raw,
-                    rects.as_ptr() as *mut _,
-                    rects.len() as _,
-                )
+                super::check_error()
             } else {
-                self.display.inner.egl.SwapBuffers(*self.display.inner.raw, self.raw)
+                Ok(())
             }
-        };
-
-        if res == egl::FALSE {
-            super::check_error()
-        } else {
-            Ok(())
         }
     }
 
@@ -334,12 +358,15 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     type SurfaceType = T;
 
     fn buffer_age(&self) -> u32 {
-        self.display
-            .inner
-            .display_extensions
-            .contains("EGL_EXT_buffer_age")
-            .then(|| unsafe { self.raw_attribute(egl::BUFFER_AGE_EXT as EGLint) })
-            .unwrap_or(0) as u32
+        // The age query is also provided by the partial update extension.
+        let extensions = &self.display.inner.display_extensions;
+        if extensions.contains("EGL_EXT_buffer_age")
+            || extensions.contains("EGL_KHR_partial_update")
+        {
+            unsafe { self.raw_attribute(egl::BUFFER_AGE_EXT as EGLint) as u32 }
+        } else {
+            0
+        }
     }
 
     fn width(&self) -> Option<u32> {
@@ -366,14 +393,55 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         }
     }
 
+    fn swap_buffers_with_damage(&self, context: &Self::Context, rects: &[Rect]) -> Result<()> {
+        context.inner.bind_api();
+
+        let res = unsafe {
+            if self.display.inner.display_extensions.contains("EGL_KHR_swap_buffers_with_damage") {
+                self.display.inner.egl.SwapBuffersWithDamageKHR(
+                    *self.display.inner.raw,
+                    self.raw,
+                    rects.as_ptr() as *mut _,
+                    rects.len() as _,
+                )
+            } else if self
+                .display
+                .inner
+                .display_extensions
+                .contains("EGL_EXT_swap_buffers_with_damage")
+            {
+                self.display.inner.egl.SwapBuffersWithDamageEXT(
+                    *self.display.inner.raw,
+                    self.raw,
+                    rects.as_ptr() as *mut _,
+                    rects.len() as _,
+                )
+            } else {
+                self.display.inner.egl.SwapBuffers(*self.display.inner.raw, self.raw)
+            }
+        };
+
+        if res == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
     fn set_swap_interval(&self, context: &Self::Context, interval: SwapInterval) -> Result<()> {
+        let interval = match interval {
+            SwapInterval::DontWait => 0,
+            SwapInterval::Wait(interval) => interval.get() as EGLint,
+            SwapInterval::Adaptive(_) => {
+                return Err(
+                    ErrorKind::NotSupported("adaptive swap interval is not supported").into()
+                );
+            },
+        };
+
         unsafe {
             context.inner.bind_api();
 
-            let interval = match interval {
-                SwapInterval::DontWait => 0,
-                SwapInterval::Wait(interval) => interval.get() as EGLint,
-            };
             if self.display.inner.egl.SwapInterval(*self.display.inner.raw, interval) == egl::FALSE
             {
                 super::check_error()
@@ -402,7 +470,9 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     }
 
     fn resize(&self, _context: &Self::Context, width: NonZeroU32, height: NonZeroU32) {
-        self.native_window.as_ref().unwrap().resize(width, height)
+        if let Some(native_window) = self.native_window.as_ref() {
+            native_window.resize(width, height)
+        }
     }
 }
 
@@ -0,0 +1,292 @@
+//! Everything related to `EGLSync`.
+
+use std::fmt;
+#[cfg(unix)]
+use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd};
+use std::time::Duration;
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLSync, EGLenum, EGLint};
+
+use crate::context::Version;
+use crate::error::{ErrorKind, Result};
+
+use super::context::PossiblyCurrentContext;
+use super::display::Display;
+
+impl Display {
+    /// Create a fence sync object.
+    ///
+    /// The fence is inserted into the command stream of the `context`, which
+    /// must be current on the calling thread. The sync object will be
+    /// signaled once all the commands issued before the fence are completed.
+    ///
+    /// This function uses the EGL 1.5 entry points when they are available and
+    /// falls back to the `EGL_KHR_fence_sync` extension otherwise, returning
+    /// [`Err`] when neither of them is supported.
+    pub fn create_fence_sync(&self, context: &PossiblyCurrentContext) -> Result<Sync> {
+        if !self.supports_core_sync()
+            && !self.inner.display_extensions.contains("EGL_KHR_fence_sync")
+        {
+            return Err(ErrorKind::NotSupported("fence sync objects are not supported").into());
+        }
+
+        context.inner.bind_api();
+
+        unsafe { self.create_sync(egl::SYNC_FENCE, &[]) }
+    }
+
+    /// Create a native fence sync object.
+    ///
+    /// The fence is inserted into the command stream of the `context`, which
+    /// must be current on the calling thread. The native fence backing it
+    /// could be exported with [`Sync::dup_native_fence_fd()`] once the
+    /// commands of the `context` were flushed.
+    ///
+    /// This function returns [`Err`] if the `EGL_ANDROID_native_fence_sync`
+    /// extension is not available.
+    #[cfg(unix)]
+    pub fn create_native_fence_sync(&self, context: &PossiblyCurrentContext) -> Result<Sync> {
+        self.check_native_fence_sync()?;
+
+        context.inner.bind_api();
+
+        unsafe { self.create_sync(egl::SYNC_NATIVE_FENCE_ANDROID, &[]) }
+    }
+
+    /// Create a native fence sync object from the native fence `fd`, which is
+    /// usually received from the other process or API.
+    ///
+    /// The resulted sync object could be waited on with
+    /// [`Sync::server_wait()`] by the `context`, which must be current on the
+    /// calling thread.
+    ///
+    /// The ownership of the `fd` is transferred to EGL on success and it's
+    /// closed otherwise.
+    ///
+    /// This function returns [`Err`] if the `EGL_ANDROID_native_fence_sync`
+    /// extension is not available.
+    #[cfg(unix)]
+    pub fn import_native_fence_sync(
+        &self,
+        context: &PossiblyCurrentContext,
+        fd: OwnedFd,
+    ) -> Result<Sync> {
+        self.check_native_fence_sync()?;
+
+        context.inner.bind_api();
+
+        let attrs = [egl::SYNC_NATIVE_FENCE_FD_ANDROID as EGLAttrib, fd.as_raw_fd() as EGLAttrib];
+        let sync = unsafe { self.create_sync(egl::SYNC_NATIVE_FENCE_ANDROID, &attrs)? };
+
+        // EGL owns the fd now.
+        let _ = fd.into_raw_fd();
+
+        Ok(sync)
+    }
+
+    #[cfg(unix)]
+    fn check_native_fence_sync(&self) -> Result<()> {
+        if self.inner.display_extensions.contains("EGL_ANDROID_native_fence_sync") {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("native fence sync objects are not supported").into())
+        }
+    }
+
+    /// Create the sync object of the given `ty` with `attrs` not terminated by
+    /// `EGL_NONE`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the sync type and its attributes are
+    /// supported by the display.
+    pub(crate) unsafe fn create_sync(&self, ty: EGLenum, attrs: &[EGLAttrib]) -> Result<Sync> {
+        let raw = unsafe {
+            if self.supports_core_sync() {
+                let mut attrs = attrs.to_vec();
+                attrs.push(egl::NONE as EGLAttrib);
+                self.inner.egl.CreateSync(*self.inner.raw, ty, attrs.as_ptr())
+            } else {
+                let mut attrs: Vec<EGLint> = attrs.iter().map(|&attr| attr as EGLint).collect();
+                attrs.push(egl::NONE as EGLint);
+                self.inner.egl.CreateSyncKHR(*self.inner.raw, ty, attrs.as_ptr())
+            }
+        };
+
+        if raw == egl::NO_SYNC {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(Sync { display: self.clone(), raw })
+    }
+
+    /// Whether the EGL 1.5 sync entry points could be used on the display.
+    fn supports_core_sync(&self) -> bool {
+        self.inner.version >= Version::new(1, 5)
+    }
+}
+
+/// A wrapper around `EGLSync`.
+///
+/// The sync object is destroyed when dropped.
+pub struct Sync {
+    display: Display,
+    raw: EGLSync,
+}
+
+// The sync object is a display level object, thus could be used from any
+// thread.
+unsafe impl Send for Sync {}
+unsafe impl std::marker::Sync for Sync {}
+
+impl Sync {
+    /// Block the calling thread until the sync object is signaled or the
+    /// `timeout` expires. Passing [`None`] will wait forever.
+    ///
+    /// The context the sync object was created on is flushed when it's current
+    /// on the calling thread, so the wait will always make progress.
+    ///
+    /// Returns `true` when the sync object was signaled and `false` when the
+    /// `timeout` has expired.
+    pub fn client_wait(&self, timeout: Option<Duration>) -> Result<bool> {
+        let timeout = match timeout {
+            // Clamp the timeout to not accidentally wait forever.
+            Some(timeout) => timeout.as_nanos().min(egl::FOREVER as u128 - 1) as u64,
+            None => egl::FOREVER,
+        };
+
+        let flags = egl::SYNC_FLUSH_COMMANDS_BIT as EGLint;
+        let status = unsafe {
+            if self.display.supports_core_sync() {
+                self.display.inner.egl.ClientWaitSync(
+                    *self.display.inner.raw,
+                    self.raw,
+                    flags,
+                    timeout,
+                )
+            } else {
+                self.display.inner.egl.ClientWaitSyncKHR(
+                    *self.display.inner.raw,
+                    self.raw,
+                    flags,
+                    timeout,
+                )
+            }
+        };
+
+        match status as EGLenum {
+            egl::CONDITION_SATISFIED => Ok(true),
+            egl::TIMEOUT_EXPIRED => Ok(false),
+            _ => Err(super::check_error().err().unwrap_or_else(|| ErrorKind::Misc.into())),
+        }
+    }
+
+    /// Make the server of the `context` wait until the sync object is signaled
+    /// without blocking the calling thread.
+    ///
+    /// The `context` must be current on the calling thread.
+    ///
+    /// This function returns [`Err`] if neither EGL 1.5 nor the
+    /// `EGL_KHR_wait_sync` extension are supported.
+    pub fn server_wait(&self, context: &PossiblyCurrentContext) -> Result<()> {
+        let display = &self.display.inner;
+        context.inner.bind_api();
+
+        let res = unsafe {
+            if self.display.supports_core_sync() {
+                display.egl.WaitSync(*display.raw, self.raw, 0)
+            } else if display.display_extensions.contains("EGL_KHR_wait_sync") {
+                display.egl.WaitSyncKHR(*display.raw, self.raw, 0) as _
+            } else {
+                return Err(ErrorKind::NotSupported("server side waits are not supported").into());
+            }
+        };
+
+        if res == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check whether the sync object is signaled without blocking.
+    pub fn is_signaled(&self) -> Result<bool> {
+        let status = self.raw_attribute(egl::SYNC_STATUS as EGLint)?;
+        Ok(status as EGLenum == egl::SIGNALED)
+    }
+
+    /// Export the native fence backing the native fence sync object.
+    ///
+    /// The commands of the context the sync object was created on must be
+    /// flushed before calling this function, otherwise the native fence is not
+    /// yet created and [`Err`] is returned.
+    ///
+    /// See [`Display::create_native_fence_sync()`].
+    #[cfg(unix)]
+    pub fn dup_native_fence_fd(&self) -> Result<OwnedFd> {
+        let display = &self.display.inner;
+        if !display.display_extensions.contains("EGL_ANDROID_native_fence_sync") {
+            return Err(
+                ErrorKind::NotSupported("native fence sync objects are not supported").into()
+            );
+        }
+
+        let fd = unsafe { display.egl.DupNativeFenceFDANDROID(*display.raw, self.raw) };
+        if fd == egl::NO_NATIVE_FENCE_FD_ANDROID {
+            return Err(super::check_error().err().unwrap_or_else(|| {
+                ErrorKind::NotSupported("the native fence is not yet available").into()
+            }));
+        }
+
+        // SAFETY: The fd was duplicated by EGL, so we own it.
+        Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+
+    /// Get a raw handle to the `EGLSync`.
+    pub fn raw_sync(&self) -> EGLSync {
+        self.raw
+    }
+
+    /// Query the sync object attribute.
+    pub(crate) fn raw_attribute(&self, attr: EGLint) -> Result<EGLAttrib> {
+        let display = &self.display.inner;
+        unsafe {
+            let res = if self.display.supports_core_sync() {
+                let mut value = 0;
+                let res = display.egl.GetSyncAttrib(*display.raw, self.raw, attr, &mut value);
+                (res, value)
+            } else {
+                let mut value = 0;
+                let res = display.egl.GetSyncAttribKHR(*display.raw, self.raw, attr, &mut value);
+                (res, value as EGLAttrib)
+            };
+
+            match res {
+                (egl::FALSE, _) => Err(super::check_error().err().unwrap()),
+                (_, value) => Ok(value),
+            }
+        }
+    }
+}
+
+impl Drop for Sync {
+    fn drop(&mut self) {
+        unsafe {
+            if self.display.supports_core_sync() {
+                self.display.inner.egl.DestroySync(*self.display.inner.raw, self.raw);
+            } else {
+                self.display.inner.egl.DestroySyncKHR(*self.display.inner.raw, self.raw);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Sync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sync")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
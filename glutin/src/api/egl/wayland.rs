@@ -0,0 +1,219 @@
+//! Everything related to the Wayland compositor side buffer sharing.
+
+use std::ptr::NonNull;
+use std::{ffi, fmt};
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLenum, EGLint};
+
+use crate::error::{ErrorKind, Result};
+
+use super::display::Display;
+use super::image::Image;
+
+impl Display {
+    /// Bind the server side `wl_display` to the display, so the clients could
+    /// share their buffers with the compositor using the `wl_drm` protocol.
+    ///
+    /// The display stays bound until the returned [`WaylandDisplayBinding`]
+    /// is dropped.
+    ///
+    /// This function returns [`Err`] if the `EGL_WL_bind_wayland_display`
+    /// extension is not available.
+    ///
+    /// # Safety
+    ///
+    /// The `wl_display` must point to the valid server side `wl_display` and
+    /// outlive the returned binding.
+    pub unsafe fn bind_wayland_display(
+        &self,
+        wl_display: NonNull<ffi::c_void>,
+    ) -> Result<WaylandDisplayBinding> {
+        self.check_bind_wayland_display()?;
+
+        let display = &self.inner;
+        if unsafe { display.egl.BindWaylandDisplayWL(*display.raw, wl_display.as_ptr()) }
+            == egl::FALSE
+        {
+            return Err(super::check_error().err().unwrap_or_else(|| ErrorKind::BadAccess.into()));
+        }
+
+        Ok(WaylandDisplayBinding { display: self.clone(), wl_display })
+    }
+
+    /// Query the attributes of the client `wl_buffer`.
+    ///
+    /// This function returns [`Err`] if the `EGL_WL_bind_wayland_display`
+    /// extension is not available or the buffer wasn't created by EGL.
+    ///
+    /// # Safety
+    ///
+    /// The `wl_buffer` must point to the valid server side `wl_resource` of
+    /// the `wl_buffer`.
+    pub unsafe fn query_wayland_buffer(
+        &self,
+        wl_buffer: NonNull<ffi::c_void>,
+    ) -> Result<WaylandBuffer> {
+        self.check_bind_wayland_display()?;
+
+        let query = |attr: EGLint| -> Option<EGLint> {
+            let display = &self.inner;
+            let mut value = 0;
+            unsafe {
+                let res = display.egl.QueryWaylandBufferWL(
+                    *display.raw,
+                    wl_buffer.as_ptr(),
+                    attr,
+                    &mut value,
+                );
+                (res != egl::FALSE).then_some(value)
+            }
+        };
+
+        let format = match query(egl::TEXTURE_FORMAT as EGLint) {
+            Some(format) => WaylandBufferFormat::from_raw(format)
+                .ok_or(ErrorKind::NotSupported("unknown wayland buffer format"))?,
+            // The drivers may fail without setting the error for the buffers they don't
+            // manage, for example, the shm ones.
+            None => {
+                return Err(super::check_error().err().unwrap_or_else(|| {
+                    ErrorKind::NotSupported("buffer is not managed by EGL").into()
+                }))
+            },
+        };
+
+        let (width, height) = match (query(egl::WIDTH as EGLint), query(egl::HEIGHT as EGLint)) {
+            (Some(width), Some(height)) => (width as u32, height as u32),
+            _ => return Err(super::check_error().err().unwrap_or_else(|| ErrorKind::Misc.into())),
+        };
+
+        // The buffer should be treated as y-inverted when the attribute is not
+        // supported.
+        let y_inverted = query(egl::WAYLAND_Y_INVERTED_WL).map(|value| value != 0).unwrap_or(true);
+
+        Ok(WaylandBuffer { width, height, format, y_inverted })
+    }
+
+    /// Create an [`Image`] from the `plane` of the client `wl_buffer`.
+    ///
+    /// The number of planes of the buffer is given by
+    /// [`WaylandBufferFormat::num_planes()`].
+    ///
+    /// This function returns [`Err`] if the `EGL_WL_bind_wayland_display`
+    /// extension is not available.
+    ///
+    /// # Safety
+    ///
+    /// The `wl_buffer` must point to the valid server side `wl_resource` of
+    /// the `wl_buffer`.
+    pub unsafe fn create_wayland_buffer_image(
+        &self,
+        wl_buffer: NonNull<ffi::c_void>,
+        plane: u32,
+    ) -> Result<Image> {
+        self.check_bind_wayland_display()?;
+
+        let attrs = [egl::WAYLAND_PLANE_WL as EGLAttrib, plane as EGLAttrib];
+        unsafe {
+            self.create_image_raw(
+                egl::NO_CONTEXT,
+                egl::WAYLAND_BUFFER_WL as EGLenum,
+                wl_buffer.as_ptr(),
+                &attrs,
+            )
+        }
+    }
+
+    fn check_bind_wayland_display(&self) -> Result<()> {
+        if self.inner.display_extensions.contains("EGL_WL_bind_wayland_display") {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("binding wayland display is not supported").into())
+        }
+    }
+}
+
+/// The guard keeping the `wl_display` bound to the [`Display`].
+///
+/// The `wl_display` is unbound when dropped.
+pub struct WaylandDisplayBinding {
+    display: Display,
+    wl_display: NonNull<ffi::c_void>,
+}
+
+impl Drop for WaylandDisplayBinding {
+    fn drop(&mut self) {
+        unsafe {
+            self.display
+                .inner
+                .egl
+                .UnbindWaylandDisplayWL(*self.display.inner.raw, self.wl_display.as_ptr());
+        }
+    }
+}
+
+impl fmt::Debug for WaylandDisplayBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaylandDisplayBinding")
+            .field("display", &self.display.inner.raw)
+            .field("wl_display", &self.wl_display)
+            .finish()
+    }
+}
+
+/// The attributes of the client `wl_buffer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WaylandBuffer {
+    /// The width of the buffer in pixels.
+    pub width: u32,
+
+    /// The height of the buffer in pixels.
+    pub height: u32,
+
+    /// The texture format of the buffer.
+    pub format: WaylandBufferFormat,
+
+    /// Whether the buffer origin is at the top left corner.
+    pub y_inverted: bool,
+}
+
+/// The texture format of the client `wl_buffer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WaylandBufferFormat {
+    /// The RGB texture.
+    Rgb,
+    /// The RGBA texture.
+    Rgba,
+    /// The three planes with Y, U and V components.
+    YuvPlanar,
+    /// The two planes with Y and interleaved UV components.
+    YuvSemiPlanar,
+    /// The two planes with Y and interleaved XUXV components.
+    YXuxv,
+    /// The texture which could only be bound to the `GL_TEXTURE_EXTERNAL_OES`
+    /// target.
+    External,
+}
+
+impl WaylandBufferFormat {
+    /// The number of planes of the buffer with the given format.
+    pub fn num_planes(self) -> u32 {
+        match self {
+            Self::Rgb | Self::Rgba | Self::External => 1,
+            Self::YuvSemiPlanar | Self::YXuxv => 2,
+            Self::YuvPlanar => 3,
+        }
+    }
+
+    fn from_raw(raw: EGLint) -> Option<Self> {
+        match raw {
+            raw if raw == egl::TEXTURE_RGB as EGLint => Some(Self::Rgb),
+            raw if raw == egl::TEXTURE_RGBA as EGLint => Some(Self::Rgba),
+            egl::TEXTURE_Y_U_V_WL => Some(Self::YuvPlanar),
+            egl::TEXTURE_Y_UV_WL => Some(Self::YuvSemiPlanar),
+            egl::TEXTURE_Y_XUXV_WL => Some(Self::YXuxv),
+            egl::TEXTURE_EXTERNAL_WL => Some(Self::External),
+            _ => None,
+        }
+    }
+}
@@ -63,6 +63,7 @@ impl Display {
             raw: GlxContext(context),
             is_gles,
             supports_surfaceless,
+            owned: true,
         };
 
         Ok(NotCurrentContext::new(inner))
@@ -241,6 +242,41 @@ pub struct NotCurrentContext {
 }
 
 impl NotCurrentContext {
+    /// Wrap the foreign `context` created with the `config`, for example, by
+    /// the other library.
+    ///
+    /// The `api` must be the one the context was created for, since GLX can't
+    /// query it. The context is not owned by glutin, thus it's not destroyed
+    /// when dropped.
+    ///
+    /// # Safety
+    ///
+    /// The `context` must be a valid `GLXContext` created with the `config`,
+    /// which is not current and outlives the returned context.
+    pub unsafe fn from_raw(config: &Config, context: GLXContext, api: ContextApi) -> Self {
+        let display = config.display();
+
+        // Surfaceless contexts are supported with the GLX_ARB_create_context extension
+        // when using OpenGL 3.0 or greater.
+        let supports_surfaceless = match api {
+            ContextApi::OpenGl(version) => {
+                display.inner.client_extensions.contains("GLX_ARB_create_context")
+                    && version.is_some_and(|version| version >= Version::new(3, 0))
+            },
+            ContextApi::Gles(_) => false,
+        };
+
+        let inner = ContextInner {
+            display,
+            config: config.clone(),
+            raw: GlxContext(context),
+            is_gles: matches!(api, ContextApi::Gles(_)),
+            supports_surfaceless,
+            owned: false,
+        };
+        Self::new(inner)
+    }
+
     fn new(inner: ContextInner) -> Self {
         Self { inner }
     }
@@ -393,6 +429,7 @@ struct ContextInner {
     raw: GlxContext,
     is_gles: bool,
     supports_surfaceless: bool,
+    owned: bool,
 }
 
 impl ContextInner {
@@ -452,6 +489,10 @@ impl ContextInner {
 
 impl Drop for ContextInner {
     fn drop(&mut self) {
+        if !self.owned {
+            return;
+        }
+
         let _ = super::last_glx_error(|| unsafe {
             self.display.inner.glx.DestroyContext(self.display.inner.raw.cast(), *self.raw);
         });
@@ -150,6 +150,12 @@ impl Display {
                 || extensions.contains("GLX_MESA_swap_control"),
         );
 
+        features.set(
+            DisplayFeatures::ADAPTIVE_SWAP_CONTROL,
+            extensions.contains("GLX_EXT_swap_control")
+                && extensions.contains("GLX_EXT_swap_control_tear"),
+        );
+
         features.set(
             DisplayFeatures::CONTEXT_ROBUSTNESS,
             extensions.contains("GLX_ARB_create_context_robustness"),
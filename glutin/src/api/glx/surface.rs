@@ -14,13 +14,14 @@ use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
 use crate::private::Sealed;
 use crate::surface::{
-    AsRawSurface, GlSurface, NativePixmap, PbufferSurface, PixmapSurface, RawSurface,
+    AsRawSurface, GlSurface, NativePixmap, PbufferSurface, PixmapSurface, RawSurface, Rect,
     SurfaceAttributes, SurfaceType, SurfaceTypeTrait, SwapInterval, WindowSurface,
 };
 
 use super::config::Config;
 use super::context::PossiblyCurrentContext;
 use super::display::Display;
+use super::GlxExtra;
 
 /// Hint for the attributes array.
 const ATTR_SIZE_HINT: usize = 8;
@@ -163,6 +164,132 @@ pub struct Surface<T: SurfaceTypeTrait> {
 unsafe impl<T: SurfaceTypeTrait> Send for Surface<T> {}
 
 impl<T: SurfaceTypeTrait> Surface<T> {
+    /// Get the current [`SyncValues`] of the surface.
+    ///
+    /// This function returns [`Err`] if the `GLX_OML_sync_control` extension
+    /// is not available.
+    pub fn sync_values(&self) -> Result<SyncValues> {
+        let extra = self.sync_control()?;
+        let mut values = SyncValues::default();
+        let success = super::last_glx_error(|| unsafe {
+            extra.GetSyncValuesOML(
+                self.display.inner.raw.cast(),
+                self.raw,
+                &mut values.ust,
+                &mut values.msc,
+                &mut values.sbc,
+            )
+        })?;
+
+        if success == 0 {
+            return Err(ErrorKind::Misc.into());
+        }
+
+        Ok(values)
+    }
+
+    /// Schedule the buffers swap once the MSC reaches `target_msc` or, when
+    /// it's already passed, once `MSC % divisor == remainder`.
+    ///
+    /// Returns the SBC the swap will complete with.
+    ///
+    /// This function returns [`Err`] if the `GLX_OML_sync_control` extension
+    /// is not available.
+    pub fn swap_buffers_msc(
+        &self,
+        _context: &PossiblyCurrentContext,
+        target_msc: i64,
+        divisor: i64,
+        remainder: i64,
+    ) -> Result<i64> {
+        let extra = self.sync_control()?;
+        let sbc = super::last_glx_error(|| unsafe {
+            extra.SwapBuffersMscOML(
+                self.display.inner.raw.cast(),
+                self.raw,
+                target_msc,
+                divisor,
+                remainder,
+            )
+        })?;
+
+        if sbc < 0 {
+            return Err(ErrorKind::Misc.into());
+        }
+
+        Ok(sbc)
+    }
+
+    /// Block until the MSC reaches `target_msc` or, when it's already passed,
+    /// until `MSC % divisor == remainder`.
+    ///
+    /// This function returns [`Err`] if the `GLX_OML_sync_control` extension
+    /// is not available.
+    pub fn wait_for_msc(
+        &self,
+        target_msc: i64,
+        divisor: i64,
+        remainder: i64,
+    ) -> Result<SyncValues> {
+        let extra = self.sync_control()?;
+        let mut values = SyncValues::default();
+        let success = super::last_glx_error(|| unsafe {
+            extra.WaitForMscOML(
+                self.display.inner.raw.cast(),
+                self.raw,
+                target_msc,
+                divisor,
+                remainder,
+                &mut values.ust,
+                &mut values.msc,
+                &mut values.sbc,
+            )
+        })?;
+
+        if success == 0 {
+            return Err(ErrorKind::Misc.into());
+        }
+
+        Ok(values)
+    }
+
+    /// Block until the SBC reaches `target_sbc`, or until all the scheduled
+    /// swaps complete when it's `0`.
+    ///
+    /// This function returns [`Err`] if the `GLX_OML_sync_control` extension
+    /// is not available.
+    pub fn wait_for_sbc(&self, target_sbc: i64) -> Result<SyncValues> {
+        let extra = self.sync_control()?;
+        let mut values = SyncValues::default();
+        let success = super::last_glx_error(|| unsafe {
+            extra.WaitForSbcOML(
+                self.display.inner.raw.cast(),
+                self.raw,
+                target_sbc,
+                &mut values.ust,
+                &mut values.msc,
+                &mut values.sbc,
+            )
+        })?;
+
+        if success == 0 {
+            return Err(ErrorKind::Misc.into());
+        }
+
+        Ok(values)
+    }
+
+    fn sync_control(&self) -> Result<&'static GlxExtra> {
+        match self.display.inner.glx_extra {
+            Some(extra)
+                if self.display.inner.client_extensions.contains("GLX_OML_sync_control") =>
+            {
+                Ok(extra)
+            },
+            _ => Err(ErrorKind::NotSupported("GLX_OML_sync_control is not supported").into()),
+        }
+    }
+
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
@@ -231,6 +358,11 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         })
     }
 
+    fn swap_buffers_with_damage(&self, context: &Self::Context, _rects: &[Rect]) -> Result<()> {
+        // The damage is not supported, so swap the entire surface.
+        self.swap_buffers(context)
+    }
+
     fn set_swap_interval(&self, _context: &Self::Context, interval: SwapInterval) -> Result<()> {
         let extra = match self.display.inner.glx_extra {
             Some(extra) if self.display.inner.features.contains(DisplayFeatures::SWAP_CONTROL) => {
@@ -245,7 +377,18 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
 
         let interval = match interval {
             SwapInterval::DontWait => 0,
-            SwapInterval::Wait(n) => n.get(),
+            SwapInterval::Wait(n) => i32::try_from(n.get()).map_err(|_| ErrorKind::BadParameter)?,
+            // Only `GLX_EXT_swap_control` accepts the negative intervals.
+            SwapInterval::Adaptive(n)
+                if self.display.inner.features.contains(DisplayFeatures::ADAPTIVE_SWAP_CONTROL) =>
+            {
+                -i32::try_from(n.get()).map_err(|_| ErrorKind::BadParameter)?
+            },
+            SwapInterval::Adaptive(_) => {
+                return Err(
+                    ErrorKind::NotSupported("adaptive swap interval is not supported").into()
+                );
+            },
         };
 
         let mut applied = false;
@@ -329,3 +472,14 @@ impl<T: SurfaceTypeTrait> AsRawSurface for Surface<T> {
 }
 
 impl<T: SurfaceTypeTrait> Sealed for Surface<T> {}
+
+/// The synchronization counters of the surface from `GLX_OML_sync_control`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SyncValues {
+    /// The Unadjusted System Time of the last MSC increment, in microseconds.
+    pub ust: i64,
+    /// The Media Stream Counter, incremented on every vertical retrace.
+    pub msc: i64,
+    /// The Swap Buffer Counter, incremented on every completed swap.
+    pub sbc: i64,
+}
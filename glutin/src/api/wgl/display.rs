@@ -114,6 +114,12 @@ impl Display {
 
         features.set(DisplayFeatures::SWAP_CONTROL, extensions.contains("WGL_EXT_swap_control"));
 
+        features.set(
+            DisplayFeatures::ADAPTIVE_SWAP_CONTROL,
+            extensions.contains("WGL_EXT_swap_control")
+                && extensions.contains("WGL_EXT_swap_control_tear"),
+        );
+
         features.set(
             DisplayFeatures::CONTEXT_ROBUSTNESS,
             extensions.contains("WGL_ARB_create_context_robustness"),
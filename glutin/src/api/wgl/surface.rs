@@ -21,7 +21,7 @@ use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
 use crate::surface::{
-    AsRawSurface, GlSurface, PbufferSurface, PixmapSurface, RawSurface, SurfaceAttributes,
+    AsRawSurface, GlSurface, PbufferSurface, PixmapSurface, RawSurface, Rect, SurfaceAttributes,
     SurfaceTypeTrait, SwapInterval, WindowSurface,
 };
 
@@ -215,6 +215,11 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         }
     }
 
+    fn swap_buffers_with_damage(&self, context: &Self::Context, _rects: &[Rect]) -> Result<()> {
+        // The damage is not supported, so swap the entire surface.
+        self.swap_buffers(context)
+    }
+
     fn set_swap_interval(&self, _context: &Self::Context, interval: SwapInterval) -> Result<()> {
         match self.raw {
             WglSurface::Window(..) => {
@@ -227,7 +232,24 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
 
                 let interval = match interval {
                     SwapInterval::DontWait => 0,
-                    SwapInterval::Wait(n) => n.get(),
+                    SwapInterval::Wait(n) => {
+                        i32::try_from(n.get()).map_err(|_| ErrorKind::BadParameter)?
+                    },
+                    SwapInterval::Adaptive(n)
+                        if self
+                            .display
+                            .inner
+                            .features
+                            .contains(DisplayFeatures::ADAPTIVE_SWAP_CONTROL) =>
+                    {
+                        -i32::try_from(n.get()).map_err(|_| ErrorKind::BadParameter)?
+                    },
+                    SwapInterval::Adaptive(_) => {
+                        return Err(ErrorKind::NotSupported(
+                            "adaptive swap interval is not supported",
+                        )
+                        .into());
+                    },
                 };
 
                 if unsafe { extra.SwapIntervalEXT(interval as _) } == 0 {
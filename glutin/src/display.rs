@@ -568,6 +568,22 @@ bitflags! {
         ///
         /// [`SRGB`]: crate::surface::SurfaceAttributesBuilder::with_srgb
         const SRGB_FRAMEBUFFERS           = 0b1000_0000;
+
+        /// The display supports declaring the damage region of the frame
+        /// before rendering it with `EGL_KHR_partial_update`.
+        const PARTIAL_UPDATE              = 0b0001_0000_0000;
+
+        /// The display supports passing the damage to the system compositor
+        /// with [`swap buffers with damage`].
+        ///
+        /// [`swap buffers with damage`]: crate::surface::GlSurface::swap_buffers_with_damage
+        const SWAP_BUFFERS_WITH_DAMAGE    = 0b0010_0000_0000;
+
+        /// The display supports the [`adaptive swap interval`], tearing when
+        /// the frame was missed.
+        ///
+        /// [`adaptive swap interval`]: crate::surface::SwapInterval::Adaptive
+        const ADAPTIVE_SWAP_CONTROL       = 0b0100_0000_0000;
     }
 }
 
@@ -1,6 +1,7 @@
 //! A cross platform OpenGL surface representation.
 #![allow(unreachable_patterns)]
 
+use std::collections::VecDeque;
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
 
@@ -59,6 +60,18 @@ pub trait GlSurface<T: SurfaceTypeTrait>: Sealed {
     /// buffered.
     fn swap_buffers(&self, context: &Self::Context) -> Result<()>;
 
+    /// Swaps the underlying back buffers when the surface is not single
+    /// buffered and pass the [`Rect`] information to the system
+    /// compositor. Providing empty slice will damage the entire surface.
+    ///
+    /// When the damage is not supported the function acts like
+    /// [`GlSurface::swap_buffers`], see
+    /// [`crate::display::DisplayFeatures::SWAP_BUFFERS_WITH_DAMAGE`].
+    ///
+    /// This Api doesn't do any partial rendering, it just provides hints for
+    /// the system compositor.
+    fn swap_buffers_with_damage(&self, context: &Self::Context, rects: &[Rect]) -> Result<()>;
+
     /// Check whether the surface is current on to the current thread.
     fn is_current(&self, context: &Self::Context) -> bool;
 
@@ -335,6 +348,28 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         }
     }
 
+    fn swap_buffers_with_damage(&self, context: &Self::Context, rects: &[Rect]) -> Result<()> {
+        match (self, context) {
+            #[cfg(egl_backend)]
+            (Self::Egl(surface), PossiblyCurrentContext::Egl(context)) => {
+                surface.swap_buffers_with_damage(context, rects)
+            },
+            #[cfg(glx_backend)]
+            (Self::Glx(surface), PossiblyCurrentContext::Glx(context)) => {
+                surface.swap_buffers_with_damage(context, rects)
+            },
+            #[cfg(cgl_backend)]
+            (Self::Cgl(surface), PossiblyCurrentContext::Cgl(context)) => {
+                surface.swap_buffers_with_damage(context, rects)
+            },
+            #[cfg(wgl_backend)]
+            (Self::Wgl(surface), PossiblyCurrentContext::Wgl(context)) => {
+                surface.swap_buffers_with_damage(context, rects)
+            },
+            _ => unreachable!(),
+        }
+    }
+
     fn set_swap_interval(&self, context: &Self::Context, interval: SwapInterval) -> Result<()> {
         match (self, context) {
             #[cfg(egl_backend)]
@@ -493,6 +528,14 @@ pub enum SwapInterval {
     /// The swap is synchronized to the `n`'th video frame. This is typically
     /// set to `1` to enable vsync and prevent screen tearing.
     Wait(NonZeroU32),
+
+    /// The swap is synchronized to the `n`'th video frame like with
+    /// [`Self::Wait`], but when the frame was missed the swap happens
+    /// immediately, allowing the tearing instead of waiting for the next one.
+    ///
+    /// Support for this variant is indicated by
+    /// [`crate::display::DisplayFeatures::ADAPTIVE_SWAP_CONTROL`].
+    Adaptive(NonZeroU32),
 }
 
 /// A platform native pixmap.
@@ -550,3 +593,160 @@ impl Rect {
         Self { x, y, width, height }
     }
 }
+
+/// The tracker of the damage of the recent frames, which computes the region
+/// to repaint from the [`GlSurface::buffer_age`].
+///
+/// The back buffer with the age `n` contains the contents of the frame
+/// rendered `n` frames ago, thus the damage of the frames rendered since
+/// then must be repainted along with the damage of the current frame.
+///
+/// ```
+/// use std::num::NonZeroU32;
+///
+/// use glutin::surface::{DamageTracker, Rect};
+///
+/// let size = NonZeroU32::new(100).unwrap();
+/// let mut tracker = DamageTracker::new(size, size, 3);
+///
+/// tracker.record_frame(&[Rect::new(0, 0, 10, 10)]);
+///
+/// // The back buffer is the one of the previous frame.
+/// let damage = [Rect::new(20, 20, 10, 10)];
+/// assert_eq!(tracker.repaint_region(1, &damage), vec![Rect::new(20, 20, 10, 10)]);
+///
+/// // The back buffer misses the previous frame as well.
+/// assert_eq!(tracker.repaint_region(2, &damage), vec![
+///     Rect::new(20, 20, 10, 10),
+///     Rect::new(0, 0, 10, 10)
+/// ]);
+///
+/// // The contents of the back buffer are unknown.
+/// assert_eq!(tracker.repaint_region(0, &damage), vec![Rect::new(0, 0, 100, 100)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DamageTracker {
+    /// The damage of the recent frames, starting from the most recent one.
+    frames: VecDeque<Vec<Rect>>,
+    max_frames: usize,
+    width: NonZeroU32,
+    height: NonZeroU32,
+    /// Whether the surface was resized since the last frame.
+    resized: bool,
+}
+
+impl DamageTracker {
+    /// Create the tracker for the surface of the given size, handling the back
+    /// buffers with the age up to `max_age`.
+    ///
+    /// The back buffers older than `max_age` frames are repainted entirely.
+    pub fn new(width: NonZeroU32, height: NonZeroU32, max_age: usize) -> Self {
+        // The back buffer with the age `n` misses the damage of `n - 1` frames.
+        let max_frames = max_age.saturating_sub(1);
+        Self {
+            frames: VecDeque::with_capacity(max_frames),
+            max_frames,
+            width,
+            height,
+            resized: false,
+        }
+    }
+
+    /// Get the rectangles which must be repainted in the current frame, given
+    /// the `buffer_age` of the surface and the `damage` of the current frame.
+    ///
+    /// The entire surface must be repainted when the age is `0`, which means
+    /// that the contents of the back buffer are unknown, when the age exceeds
+    /// the tracked frames, or when the surface was resized. Providing empty
+    /// slice will damage the entire surface.
+    pub fn repaint_region(&self, buffer_age: u32, damage: &[Rect]) -> Vec<Rect> {
+        let age = buffer_age as usize;
+        if damage.is_empty() || self.resized || age == 0 || age - 1 > self.frames.len() {
+            return vec![self.surface_rect()];
+        }
+
+        damage.iter().chain(self.frames.iter().take(age - 1).flatten()).copied().collect()
+    }
+
+    /// Swap the buffers of the `surface` passing the `damage` of the current
+    /// frame to the system compositor and record it.
+    ///
+    /// See [`GlSurface::swap_buffers_with_damage`] for details.
+    pub fn swap_buffers_with_damage<T, S>(
+        &mut self,
+        surface: &S,
+        context: &S::Context,
+        damage: &[Rect],
+    ) -> Result<()>
+    where
+        T: SurfaceTypeTrait,
+        S: GlSurface<T>,
+    {
+        surface.swap_buffers_with_damage(context, damage)?;
+        self.record_frame(damage);
+        Ok(())
+    }
+
+    /// Record the `damage` of the frame which was just presented.
+    ///
+    /// Providing empty slice will damage the entire surface.
+    pub fn record_frame(&mut self, damage: &[Rect]) {
+        let damage = if damage.is_empty() { vec![self.surface_rect()] } else { damage.to_vec() };
+
+        self.frames.push_front(damage);
+        self.frames.truncate(self.max_frames);
+        self.resized = false;
+    }
+
+    /// Update the size of the surface.
+    ///
+    /// The contents of the back buffers are unknown after the resize, thus the
+    /// entire surface is repainted in the next frame. Resizing to the same
+    /// size keeps the recorded frames.
+    ///
+    /// ```
+    /// use std::num::NonZeroU32;
+    ///
+    /// use glutin::surface::{DamageTracker, Rect};
+    ///
+    /// let size = NonZeroU32::new(100).unwrap();
+    /// let mut tracker = DamageTracker::new(size, size, 3);
+    /// let damage = [Rect::new(20, 20, 10, 10)];
+    ///
+    /// tracker.record_frame(&[Rect::new(0, 0, 10, 10)]);
+    /// tracker.resize(size, size);
+    /// assert_eq!(tracker.repaint_region(2, &damage), vec![
+    ///     Rect::new(20, 20, 10, 10),
+    ///     Rect::new(0, 0, 10, 10)
+    /// ]);
+    ///
+    /// // The entire surface of the new size is repainted after the resize.
+    /// let new_size = NonZeroU32::new(200).unwrap();
+    /// tracker.resize(new_size, new_size);
+    /// assert_eq!(tracker.repaint_region(1, &damage), vec![Rect::new(0, 0, 200, 200)]);
+    ///
+    /// // The tracking resumes once the frame of the new size is recorded, but the
+    /// // back buffers rendered before the resize are still repainted entirely.
+    /// tracker.record_frame(&[Rect::new(50, 50, 10, 10)]);
+    /// assert_eq!(tracker.repaint_region(1, &damage), vec![Rect::new(20, 20, 10, 10)]);
+    /// assert_eq!(tracker.repaint_region(2, &damage), vec![
+    ///     Rect::new(20, 20, 10, 10),
+    ///     Rect::new(50, 50, 10, 10)
+    /// ]);
+    /// assert_eq!(tracker.repaint_region(3, &damage), vec![Rect::new(0, 0, 200, 200)]);
+    /// ```
+    pub fn resize(&mut self, width: NonZeroU32, height: NonZeroU32) {
+        if self.width == width && self.height == height {
+            return;
+        }
+
+        self.width = width;
+        self.height = height;
+        self.frames.clear();
+        self.resized = true;
+    }
+
+    fn surface_rect(&self) -> Rect {
+        Rect::new(0, 0, self.width.get() as i32, self.height.get() as i32)
+    }
+}
@@ -20,6 +20,7 @@ fn main() {
     {
         let mut file = File::create(dest.join("egl_bindings.rs")).unwrap();
         let reg = Registry::new(Api::Egl, (1, 5), Profile::Core, Fallbacks::All, [
+            "EGL_ANDROID_blob_cache",
             "EGL_ANDROID_native_fence_sync",
             "EGL_EXT_buffer_age",
             "EGL_EXT_create_context_robustness",
@@ -29,25 +30,41 @@ fn main() {
             "EGL_EXT_device_enumeration",
             "EGL_EXT_device_query",
             "EGL_EXT_device_query_name",
+            "EGL_EXT_image_dma_buf_import",
+            "EGL_EXT_image_dma_buf_import_modifiers",
+            "EGL_EXT_output_base",
+            "EGL_EXT_output_drm",
             "EGL_EXT_pixel_format_float",
             "EGL_EXT_platform_base",
             "EGL_EXT_platform_device",
             "EGL_EXT_platform_wayland",
             "EGL_EXT_platform_x11",
+            "EGL_EXT_stream_consumer_egloutput",
             "EGL_EXT_swap_buffers_with_damage",
             "EGL_IMG_context_priority",
             "EGL_KHR_create_context",
             "EGL_KHR_create_context_no_error",
             "EGL_KHR_display_reference",
             "EGL_KHR_fence_sync",
+            "EGL_KHR_gl_renderbuffer_image",
+            "EGL_KHR_gl_texture_2D_image",
+            "EGL_KHR_gl_texture_3D_image",
+            "EGL_KHR_gl_texture_cubemap_image",
             "EGL_KHR_image_base",
+            "EGL_KHR_partial_update",
             "EGL_KHR_platform_android",
             "EGL_KHR_platform_gbm",
             "EGL_KHR_platform_wayland",
             "EGL_KHR_platform_x11",
+            "EGL_KHR_stream",
+            "EGL_KHR_stream_consumer_gltexture",
+            "EGL_KHR_stream_cross_process_fd",
+            "EGL_KHR_stream_producer_eglsurface",
             "EGL_KHR_swap_buffers_with_damage",
             "EGL_KHR_wait_sync",
+            "EGL_MESA_image_dma_buf_export",
             "EGL_MESA_platform_gbm",
+            "EGL_MESA_platform_surfaceless",
             "EGL_NV_context_priority_realtime",
         ]);
 
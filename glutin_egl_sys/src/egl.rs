@@ -23,6 +23,11 @@ pub const PLATFORM_XCB_EXT: super::EGLenum = 0x31DC;
 pub const PLATFORM_XCB_SCREEN_EXT: super::EGLenum = 0x31DE;
 // EGL_EXT_device_query_name
 pub const RENDERER_EXT: super::EGLenum = 0x335F;
+// EGL_EXT_device_persistent_id
+pub const DEVICE_UUID_EXT: super::EGLenum = 0x335C;
+pub const DRIVER_UUID_EXT: super::EGLenum = 0x335D;
+pub const DRIVER_NAME_EXT: super::EGLenum = 0x335E;
+pub const UUID_SIZE_EXT: usize = 16;
 // EGL_ANGLE_platform_angle - https://chromium.googlesource.com/angle/angle/+/HEAD/extensions/EGL_ANGLE_platform_angle.txt
 pub const PLATFORM_ANGLE_ANGLE: super::EGLenum = 0x3202;
 pub const PLATFORM_ANGLE_TYPE_ANGLE: super::EGLenum = 0x3203;
@@ -34,6 +39,15 @@ pub const PLATFORM_ANGLE_NATIVE_PLATFORM_TYPE_ANGLE: super::EGLenum = 0x348F;
 pub const PLATFORM_ANGLE_TYPE_DEFAULT_ANGLE: super::EGLenum = 0x3206;
 pub const PLATFORM_ANGLE_DEVICE_TYPE_HARDWARE_ANGLE: super::EGLenum = 0x320A;
 pub const PLATFORM_ANGLE_DEVICE_TYPE_NULL_ANGLE: super::EGLenum = 0x345E;
+pub const PLATFORM_ANGLE_TYPE_D3D9_ANGLE: super::EGLenum = 0x3207;
+pub const PLATFORM_ANGLE_TYPE_D3D11_ANGLE: super::EGLenum = 0x3208;
+pub const PLATFORM_ANGLE_TYPE_OPENGL_ANGLE: super::EGLenum = 0x320D;
+pub const PLATFORM_ANGLE_TYPE_OPENGLES_ANGLE: super::EGLenum = 0x320E;
+pub const PLATFORM_ANGLE_TYPE_METAL_ANGLE: super::EGLenum = 0x3489;
+pub const PLATFORM_ANGLE_DEVICE_TYPE_ANGLE: super::EGLenum = 0x3209;
+pub const PLATFORM_ANGLE_DEVICE_TYPE_D3D_WARP_ANGLE: super::EGLenum = 0x320B;
+pub const PLATFORM_ANGLE_DEVICE_TYPE_D3D_REFERENCE_ANGLE: super::EGLenum = 0x320C;
+pub const PLATFORM_ANGLE_DEVICE_TYPE_SWIFTSHADER_ANGLE: super::EGLenum = 0x3487;
 
 mod wayland_storage {
     use super::FnPtr;
@@ -52,7 +66,75 @@ mod wayland_storage {
         FnPtr { f: super::missing_fn_panic as *const raw::c_void, is_loaded: false };
 }
 
+mod device_persistent_id_storage {
+    use super::__gl_imports::raw;
+    use super::FnPtr;
+
+    // EGL_EXT_device_persistent_id
+    pub static mut QUERY_DEVICE_BINARY_EXT: FnPtr =
+        FnPtr { f: super::missing_fn_panic as *const raw::c_void, is_loaded: false };
+}
+
+mod query_driver_storage {
+    use super::__gl_imports::raw;
+    use super::FnPtr;
+
+    // EGL_MESA_query_driver
+    pub static mut GET_DISPLAY_DRIVER_NAME: FnPtr =
+        FnPtr { f: super::missing_fn_panic as *const raw::c_void, is_loaded: false };
+    pub static mut GET_DISPLAY_DRIVER_CONFIG: FnPtr =
+        FnPtr { f: super::missing_fn_panic as *const raw::c_void, is_loaded: false };
+}
+
 impl Egl {
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn QueryDeviceBinaryEXT(
+        &self,
+        device: types::EGLDeviceEXT,
+        name: types::EGLint,
+        max_size: types::EGLint,
+        value: *mut __gl_imports::raw::c_void,
+        size: *mut types::EGLint,
+    ) -> types::EGLBoolean {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(
+                types::EGLDeviceEXT,
+                types::EGLint,
+                types::EGLint,
+                *mut __gl_imports::raw::c_void,
+                *mut types::EGLint,
+            ) -> types::EGLBoolean,
+        >(device_persistent_id_storage::QUERY_DEVICE_BINARY_EXT.f)(
+            device, name, max_size, value, size,
+        )
+    }
+
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn GetDisplayDriverName(
+        &self,
+        dpy: types::EGLDisplay,
+    ) -> *const __gl_imports::raw::c_char {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(types::EGLDisplay) -> *const __gl_imports::raw::c_char,
+        >(query_driver_storage::GET_DISPLAY_DRIVER_NAME.f)(dpy)
+    }
+
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn GetDisplayDriverConfig(
+        &self,
+        dpy: types::EGLDisplay,
+    ) -> *mut __gl_imports::raw::c_char {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(types::EGLDisplay) -> *mut __gl_imports::raw::c_char,
+        >(query_driver_storage::GET_DISPLAY_DRIVER_CONFIG.f)(dpy)
+    }
+
     #[allow(non_snake_case, unused_variables, dead_code)]
     #[inline]
     pub unsafe fn CreateWaylandBufferFromImageWL(
@@ -228,6 +310,81 @@ pub mod QueryWaylandBufferWL {
     }
 }
 
+// Extension: EGL_EXT_device_persistent_id
+//
+
+#[allow(non_snake_case)]
+pub mod QueryDeviceBinaryEXT {
+    use super::__gl_imports::raw;
+    use super::{device_persistent_id_storage, metaloadfn, FnPtr};
+
+    #[inline]
+    #[allow(dead_code)]
+    pub fn is_loaded() -> bool {
+        unsafe { device_persistent_id_storage::QUERY_DEVICE_BINARY_EXT.is_loaded }
+    }
+
+    #[allow(dead_code)]
+    pub fn load_with<F>(mut loadfn: F)
+    where
+        F: FnMut(&'static str) -> *const raw::c_void,
+    {
+        unsafe {
+            device_persistent_id_storage::QUERY_DEVICE_BINARY_EXT =
+                FnPtr::new(metaloadfn(&mut loadfn, "eglQueryDeviceBinaryEXT", &[]))
+        }
+    }
+}
+
+// Extension: EGL_MESA_query_driver
+//
+
+#[allow(non_snake_case)]
+pub mod GetDisplayDriverName {
+    use super::__gl_imports::raw;
+    use super::{metaloadfn, query_driver_storage, FnPtr};
+
+    #[inline]
+    #[allow(dead_code)]
+    pub fn is_loaded() -> bool {
+        unsafe { query_driver_storage::GET_DISPLAY_DRIVER_NAME.is_loaded }
+    }
+
+    #[allow(dead_code)]
+    pub fn load_with<F>(mut loadfn: F)
+    where
+        F: FnMut(&'static str) -> *const raw::c_void,
+    {
+        unsafe {
+            query_driver_storage::GET_DISPLAY_DRIVER_NAME =
+                FnPtr::new(metaloadfn(&mut loadfn, "eglGetDisplayDriverName", &[]))
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+pub mod GetDisplayDriverConfig {
+    use super::__gl_imports::raw;
+    use super::{metaloadfn, query_driver_storage, FnPtr};
+
+    #[inline]
+    #[allow(dead_code)]
+    pub fn is_loaded() -> bool {
+        unsafe { query_driver_storage::GET_DISPLAY_DRIVER_CONFIG.is_loaded }
+    }
+
+    #[allow(dead_code)]
+    pub fn load_with<F>(mut loadfn: F)
+    where
+        F: FnMut(&'static str) -> *const raw::c_void,
+    {
+        unsafe {
+            query_driver_storage::GET_DISPLAY_DRIVER_CONFIG =
+                FnPtr::new(metaloadfn(&mut loadfn, "eglGetDisplayDriverConfig", &[]))
+        }
+    }
+}
+
 /// OpenGL function loader.
 ///
 /// This is based on the loader generated by `gl_generator`.
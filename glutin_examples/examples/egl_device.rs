@@ -8,7 +8,7 @@ mod example {
     use std::fs::OpenOptions;
     use std::path::Path;
 
-    use glutin::api::egl::device::Device;
+    use glutin::api::egl::device::{Device, DeviceSelector};
     use glutin::api::egl::display::Display;
     use glutin::config::{ConfigSurfaceTypes, ConfigTemplate, ConfigTemplateBuilder};
     use glutin::context::{ContextApi, ContextAttributesBuilder};
@@ -29,11 +29,17 @@ mod example {
             );
         }
 
-        let device = devices.first().expect("No available devices");
+        // Prefer the hardware device and fall back to the software one.
+        let device = DeviceSelector::new()
+            .prefer_hardware()
+            .select_from(devices)
+            .expect("No available devices");
+
+        println!("Picked device: {}", device.name().unwrap_or("UNKNOWN"));
 
         // Create a display using the device.
         let display =
-            unsafe { Display::with_device(device, None) }.expect("Failed to create display");
+            unsafe { Display::with_device(&device, None) }.expect("Failed to create display");
 
         let template = config_template();
         let config = unsafe { display.find_configs(template) }
@@ -35,6 +35,7 @@ fn main() {
             "GLX_EXT_framebuffer_sRGB",
             "GLX_EXT_swap_control",
             "GLX_MESA_swap_control",
+            "GLX_OML_sync_control",
             "GLX_SGI_swap_control",
         ])
         .write_bindings(gl_generator::StructGenerator, &mut file)